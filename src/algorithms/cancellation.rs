@@ -0,0 +1,196 @@
+use std::{
+    cell::Cell,
+    collections::LinkedList,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use crate::Board;
+
+use super::observer::{SearchObserver, SearchStats};
+
+/// Shared flag that stops a running solve from any thread. Clones observe the same flag.
+///
+/// As an observer the token only answers [`SearchObserver::is_cancelled`], so it is usually
+/// paired with another observer, e.g. `(SearchStats::new(), token.clone())`.
+#[derive(Debug, Default, Clone)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+impl SearchObserver for CancellationToken {
+    fn is_cancelled(&self) -> bool {
+        CancellationToken::is_cancelled(self)
+    }
+}
+
+/// Token as seen by a single solve, remembering whether the solver was ever told to stop, so a
+/// search that ran out of boards before the token flipped is not reported as cancelled.
+pub(crate) struct CancellationWatch {
+    token: CancellationToken,
+    stopped: Cell<bool>,
+}
+
+impl CancellationWatch {
+    pub(crate) fn new(token: &CancellationToken) -> Self {
+        Self {
+            token: token.clone(),
+            stopped: Cell::new(false),
+        }
+    }
+
+    /// Whether the solver stopped because of the token.
+    pub(crate) fn stopped(&self) -> bool {
+        self.stopped.get()
+    }
+}
+
+impl SearchObserver for CancellationWatch {
+    fn is_cancelled(&self) -> bool {
+        let cancelled = self.token.is_cancelled();
+        self.stopped.set(self.stopped.get() || cancelled);
+        cancelled
+    }
+}
+
+/// Asks the search to stop once it has expanded the given number of boards, to check that a
+/// solver keeps polling for cancellation while it searches rather than only before it starts.
+#[cfg(test)]
+pub(crate) struct CancelAfter(pub(crate) usize);
+
+#[cfg(test)]
+impl SearchObserver for CancelAfter {
+    fn on_expand(&mut self, _board: &Board) {
+        self.0 = self.0.saturating_sub(1);
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum SolveOutcome {
+    Solved(LinkedList<Board>),
+    /// The whole reachable space was searched without reaching the goal.
+    Exhausted,
+    Cancelled,
+}
+
+/// Outcome of a cancellable solve together with the statistics gathered until it stopped.
+#[derive(Debug, Clone)]
+pub struct SearchResult {
+    pub outcome: SolveOutcome,
+    pub stats: SearchStats,
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::LinkedList, thread, time::Duration};
+
+    use crate::{
+        algorithms::{
+            informed_search::heuristic::mahattan_distance::MahattanDistance,
+            observer::{SearchObserver, SearchStats},
+        },
+        board::parser::BoardParser,
+        AStarAlgorithms, Algorithms, Board, BoardBuilder, BoardConfigBuilder, DfsAlgorithms,
+        GoalLayout, Scramble, UcsAlgorithms,
+    };
+
+    use super::{CancellationToken, SolveOutcome};
+
+    #[test]
+    fn it_should_stop_every_solver_once_cancelled() {
+        let solvers: Vec<Box<dyn Algorithms>> = vec![
+            Box::new(UcsAlgorithms::new()),
+            Box::new(DfsAlgorithms::new()),
+            Box::new(AStarAlgorithms::with(Box::new(MahattanDistance::new()))),
+        ];
+        for mut solver in solvers {
+            let board = BoardParser::parse("8 6 7 2 5 4 3 0 1", true).unwrap();
+            let token = CancellationToken::new();
+            token.cancel();
+            let result = solver.solve_until_cancelled(board, &token);
+            assert!(matches!(result.outcome, SolveOutcome::Cancelled));
+            assert_eq!(result.stats, SearchStats::new());
+        }
+    }
+
+    #[test]
+    fn it_should_be_cancelled_from_another_thread() {
+        // a random 15-puzzle is far beyond what breadth-first search finishes in a test
+        let config = BoardConfigBuilder::builder()
+            .layout(4, GoalLayout::RowMajor)
+            .build()
+            .unwrap();
+        let board = BoardBuilder::builder()
+            .config(config)
+            .scramble(Scramble::Uniform)
+            .seed(1)
+            .build()
+            .unwrap();
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        let handle = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            canceller.cancel();
+        });
+
+        let result = UcsAlgorithms::new().solve_until_cancelled(board, &token);
+        handle.join().unwrap();
+        assert!(matches!(result.outcome, SolveOutcome::Cancelled));
+        assert!(result.stats.expanded > 0);
+    }
+
+    /// Runs out of boards without ever polling, and only then cancels `token`.
+    struct LateCancel(CancellationToken);
+
+    impl Algorithms for LateCancel {
+        fn solve(&mut self, _initial_state: Board) -> Option<LinkedList<Board>> {
+            None
+        }
+
+        fn solve_with(
+            &mut self,
+            _initial_state: Board,
+            _observer: &mut dyn SearchObserver,
+        ) -> Option<LinkedList<Board>> {
+            self.0.cancel();
+            None
+        }
+    }
+
+    #[test]
+    fn it_should_not_report_exhausted_searches_as_cancelled() {
+        let board = BoardParser::parse("1 2 3 4 5 6 0 7 8", false).unwrap();
+        let token = CancellationToken::new();
+        let result = LateCancel(token.clone()).solve_until_cancelled(board, &token);
+        assert!(token.is_cancelled());
+        assert!(matches!(result.outcome, SolveOutcome::Exhausted));
+    }
+
+    #[test]
+    fn it_should_solve_when_never_cancelled() {
+        let board = BoardParser::parse("1 2 3 4 5 6 0 7 8", false).unwrap();
+        let result = UcsAlgorithms::new().solve_until_cancelled(board, &CancellationToken::new());
+        assert!(matches!(result.outcome, SolveOutcome::Solved(path) if path.len() == 3));
+        assert_eq!(result.stats.solution_length, Some(2));
+    }
+}
@@ -0,0 +1,199 @@
+use std::{
+    collections::{HashMap, HashSet, LinkedList},
+    fmt::Write,
+    fs, io,
+    path::Path,
+};
+
+use crate::{
+    board::{board::BoardManager, cell::Tile, moves::Move},
+    Board,
+};
+
+use super::observer::SearchObserver;
+
+struct Node {
+    tiles: Vec<Tile>,
+    n: usize,
+    parent: Option<usize>,
+    step: Option<Move>,
+    g: usize,
+    h: Option<usize>,
+    expansion: Option<usize>,
+}
+
+/// Records the part of the state graph a solver explores and renders it as a Graphviz DOT
+/// graph.
+///
+/// Like the `parents` maps the solvers keep, every state remembers the parent it was last
+/// generated from, so the recorded graph is the search tree the solver would reconstruct its
+/// path from. Nodes show the tiles, `g`, `h`, `f` (for informed boards) and the order in which
+/// they were expanded; the solution path is highlighted.
+#[derive(Default)]
+pub struct DotRecorder {
+    nodes: Vec<Node>,
+    ids: HashMap<Vec<Tile>, usize>,
+    expansions: usize,
+    solution: HashSet<usize>,
+}
+
+impl DotRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of distinct states recorded so far.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    fn node_of(&mut self, board: &Board) -> usize {
+        let tiles = BoardManager::tiles_of(board);
+        if let Some(id) = self.ids.get(tiles) {
+            return *id;
+        }
+        let id = self.nodes.len();
+        self.ids.insert(tiles.to_vec(), id);
+        self.nodes.push(Node {
+            tiles: tiles.to_vec(),
+            n: BoardManager::size_of(board) as usize,
+            parent: None,
+            step: None,
+            g: 0,
+            h: Self::heuristic_of(board),
+            expansion: None,
+        });
+        id
+    }
+
+    fn heuristic_of(board: &Board) -> Option<usize> {
+        BoardManager::is_informed(board).then(|| BoardManager::heuristic_value_of(board))
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        writeln!(dot, "digraph search {{").unwrap();
+        writeln!(dot, "  node [shape=box, fontname=\"monospace\"];").unwrap();
+        for (id, node) in self.nodes.iter().enumerate() {
+            let grid: Vec<String> = node
+                .tiles
+                .chunks(node.n)
+                .map(|row| {
+                    row.iter()
+                        .map(|tile| match tile {
+                            Tile::Empty => " ".to_string(),
+                            Tile::Value(value) => value.to_string(),
+                        })
+                        .collect::<Vec<String>>()
+                        .join(" ")
+                })
+                .collect();
+            let mut label = grid.join("\\n");
+            match node.h {
+                Some(h) => write!(label, "\\ng={} h={} f={}", node.g, h, node.g + h).unwrap(),
+                None => write!(label, "\\ng={}", node.g).unwrap(),
+            }
+            if let Some(expansion) = node.expansion {
+                write!(label, "\\n#{}", expansion).unwrap();
+            }
+            let style = if self.solution.contains(&id) {
+                ", color=red, penwidth=2"
+            } else if node.expansion.is_none() {
+                ", style=dashed"
+            } else {
+                ""
+            };
+            writeln!(dot, "  n{} [label=\"{}\"{}];", id, label, style).unwrap();
+        }
+        for (id, node) in self.nodes.iter().enumerate() {
+            if let (Some(parent), Some(step)) = (node.parent, node.step) {
+                let style = if self.solution.contains(&id) && self.solution.contains(&parent) {
+                    ", color=red, penwidth=2"
+                } else {
+                    ""
+                };
+                writeln!(
+                    dot,
+                    "  n{} -> n{} [label=\"{}\"{}];",
+                    parent, id, step, style
+                )
+                .unwrap();
+            }
+        }
+        writeln!(dot, "}}").unwrap();
+        dot
+    }
+
+    pub fn write_dot<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_dot())
+    }
+}
+
+impl SearchObserver for DotRecorder {
+    fn on_expand(&mut self, board: &Board) {
+        let id = self.node_of(board);
+        self.expansions += 1;
+        self.nodes[id].expansion.get_or_insert(self.expansions);
+    }
+
+    fn on_generate(&mut self, board: &Board, parent: &Board) {
+        let parent_id = self.node_of(parent);
+        let id = self.node_of(board);
+        if id == parent_id {
+            return;
+        }
+        let g = self.nodes[parent_id].g + 1;
+        let node = &mut self.nodes[id];
+        node.parent = Some(parent_id);
+        node.step = BoardManager::move_of(parent, board);
+        node.g = g;
+        node.h = Self::heuristic_of(board);
+    }
+
+    fn on_solution(&mut self, path: &LinkedList<Board>) {
+        self.solution = path.iter().map(|board| self.node_of(board)).collect();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        algorithms::informed_search::heuristic::mahattan_distance::MahattanDistance,
+        board::parser::BoardParser, AStarAlgorithms, Algorithms, UcsAlgorithms,
+    };
+
+    use super::DotRecorder;
+
+    #[test]
+    fn it_should_render_the_explored_tree() {
+        let board = BoardParser::parse("1 2 3 4 5 6 7 0 8", false).unwrap();
+        let mut recorder = DotRecorder::new();
+        UcsAlgorithms::new()
+            .solve_with(board, &mut recorder)
+            .unwrap();
+
+        let dot = recorder.to_dot();
+        assert!(dot.starts_with("digraph search {"));
+        assert!(dot.contains("n0 [label=\"1 2 3\\n4 5 6\\n7   8\\ng=0\\n#1\", color=red"));
+        assert!(dot.contains("n0 -> n1 [label=\"L\"]"));
+        assert!(dot.contains("[label=\"R\", color=red, penwidth=2]"));
+        assert_eq!(dot.matches("->").count(), recorder.len() - 1);
+    }
+
+    #[test]
+    fn it_should_show_heuristic_values_of_informed_boards() {
+        let board = BoardParser::parse("1 2 3 4 5 6 0 7 8", true).unwrap();
+        let mut recorder = DotRecorder::new();
+        AStarAlgorithms::with(Box::new(MahattanDistance::new()))
+            .solve_with(board, &mut recorder)
+            .unwrap();
+
+        let dot = recorder.to_dot();
+        assert!(dot.contains("g=0 h="));
+        assert!(dot.contains("g=2 h="));
+    }
+}
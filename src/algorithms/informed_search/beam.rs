@@ -0,0 +1,196 @@
+use std::collections::{HashMap, HashSet, LinkedList};
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    algorithms::{observer::SearchObserver, Algorithms},
+    board::{
+        board::{Board, BoardManager},
+        cell::Tile,
+    },
+};
+
+use super::heuristic::HeuristicFn;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BeamSelection {
+    /// Keep the states with the lowest heuristic values.
+    #[default]
+    Best,
+    /// Draw the states at random, favouring lower heuristic values, from a generator seeded
+    /// with this value so runs can be reproduced.
+    Stochastic(u64),
+}
+
+/// Beam search: a breadth-first search that only keeps `width` states per depth, chosen by
+/// their heuristic value.
+///
+/// Memory and time grow linearly with the width, which makes it usable on boards far too large
+/// for the optimal solvers, at the price of longer and not always found solutions. Wider beams
+/// usually give shorter paths; the length is reported by [`crate::SolveReport`] and
+/// [`crate::SearchStats`].
+pub struct BeamSearchAlgorithms {
+    heuristic: Box<dyn HeuristicFn>,
+    width: usize,
+    selection: BeamSelection,
+}
+
+impl BeamSearchAlgorithms {
+    pub fn with(heuristic: Box<dyn HeuristicFn>, width: usize) -> Self {
+        Self {
+            heuristic,
+            width: width.max(1),
+            selection: BeamSelection::default(),
+        }
+    }
+
+    pub fn selection(mut self, selection: BeamSelection) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Picks the next beam from `(heuristic value, board)` candidates.
+    fn select(&self, mut candidates: Vec<(usize, Board)>, rng: &mut ChaCha8Rng) -> Vec<Board> {
+        // sorting by tiles as well keeps the beam independent of the generation order
+        candidates.sort_by(|(a_weight, a), (b_weight, b)| {
+            a_weight
+                .cmp(b_weight)
+                .then_with(|| BoardManager::tiles_of(a).cmp(BoardManager::tiles_of(b)))
+        });
+        if candidates.len() <= self.width {
+            return candidates.into_iter().map(|(_, board)| board).collect();
+        }
+        match self.selection {
+            BeamSelection::Best => candidates
+                .into_iter()
+                .take(self.width)
+                .map(|(_, board)| board)
+                .collect(),
+            BeamSelection::Stochastic(_) => {
+                // weighted sampling without replacement, keeping the largest ln(u) / w, where
+                // each state weighs e^-(h - best h)
+                let best = candidates[0].0;
+                let mut keyed: Vec<(f64, Board)> = candidates
+                    .into_iter()
+                    .map(|(weight, board)| {
+                        let penalty = ((weight - best) as f64).exp();
+                        (rng.gen::<f64>().ln() * penalty, board)
+                    })
+                    .collect();
+                keyed.sort_by(|a, b| b.0.total_cmp(&a.0));
+                keyed
+                    .into_iter()
+                    .take(self.width)
+                    .map(|(_, board)| board)
+                    .collect()
+            }
+        }
+    }
+}
+
+impl Algorithms for BeamSearchAlgorithms {
+    fn solve_with(
+        &mut self,
+        initial_state: Board,
+        observer: &mut dyn SearchObserver,
+    ) -> Option<LinkedList<Board>> {
+        let mut rng = ChaCha8Rng::seed_from_u64(match self.selection {
+            BeamSelection::Best => 0,
+            BeamSelection::Stochastic(seed) => seed,
+        });
+        let weight = self.heuristic.compute(&initial_state, None);
+        let initial_state = BoardManager::assign_weight(initial_state, weight);
+        let mut visited: HashSet<Vec<Tile>> =
+            HashSet::from([BoardManager::tiles_of(&initial_state).to_vec()]);
+        let mut parents: HashMap<Vec<Tile>, Board> = HashMap::new();
+        let mut goal = initial_state.match_goal().then(|| initial_state.clone());
+        let mut beam = vec![initial_state];
+
+        while goal.is_none() && !beam.is_empty() {
+            if observer.is_cancelled() {
+                return None;
+            }
+            let mut candidates = Vec::new();
+            for current in &beam {
+                observer.on_expand(current);
+                for board in BoardManager::neigbors_of(current) {
+                    let tiles = BoardManager::tiles_of(&board).to_vec();
+                    if visited.contains(&tiles) {
+                        observer.on_duplicate(&board);
+                        continue;
+                    }
+                    observer.on_generate(&board, current);
+                    let weight = self.heuristic.compute(&board, Some(current));
+                    let board = BoardManager::assign_weight(board, weight);
+                    visited.insert(tiles.clone());
+                    parents.insert(tiles, current.clone());
+                    if board.match_goal() {
+                        goal = Some(board.clone());
+                    }
+                    candidates.push((weight, board));
+                }
+            }
+            beam = self.select(candidates, &mut rng);
+        }
+
+        let mut current = goal?;
+        let mut solutions = LinkedList::new();
+        solutions.push_front(current.clone());
+        while let Some(parent) = parents.get(BoardManager::tiles_of(&current)) {
+            solutions.push_front(parent.clone());
+            current = parent.clone();
+        }
+        observer.on_solution(&solutions);
+        Some(solutions)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        algorithms::informed_search::heuristic::mahattan_distance::MahattanDistance,
+        board::scrambled, Algorithms, GoalLayout, Scramble, SolveReport,
+    };
+
+    use super::{BeamSearchAlgorithms, BeamSelection};
+
+    #[test]
+    fn it_should_solve_large_boards() {
+        let board = scrambled(6, GoalLayout::RowMajor, Scramble::Walk(200), 3);
+        let path = BeamSearchAlgorithms::with(Box::new(MahattanDistance::new()), 32)
+            .solve(board.clone())
+            .unwrap();
+        let report = SolveReport::from_path(&path).unwrap();
+        assert_eq!(report.initial, board);
+        assert!(path.back().unwrap().match_goal());
+        assert!(report.length > 0);
+    }
+
+    #[test]
+    fn it_should_find_shorter_paths_with_wider_beams() {
+        let board = scrambled(3, GoalLayout::RowMajor, Scramble::Distance(16), 5);
+        let length = |width| {
+            BeamSearchAlgorithms::with(Box::new(MahattanDistance::new()), width)
+                .solve(board.clone())
+                .map(|path| SolveReport::from_path(&path).unwrap().length)
+        };
+        // a beam wider than any layer is a breadth-first search
+        assert_eq!(length(200_000), Some(16));
+        assert!(length(4).unwrap() >= 16);
+    }
+
+    #[test]
+    fn it_should_reproduce_stochastic_runs_from_the_seed() {
+        let board = scrambled(5, GoalLayout::RowMajor, Scramble::Walk(100), 1);
+        let solve = |seed| {
+            BeamSearchAlgorithms::with(Box::new(MahattanDistance::new()), 16)
+                .selection(BeamSelection::Stochastic(seed))
+                .solve(board.clone())
+                .map(|path| SolveReport::from_path(&path).unwrap().moves)
+        };
+        let first = solve(7);
+        assert!(first.is_some());
+        assert_eq!(first, solve(7));
+    }
+}
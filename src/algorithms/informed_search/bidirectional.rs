@@ -0,0 +1,248 @@
+use std::collections::{BTreeSet, HashMap, LinkedList};
+
+use crate::{
+    algorithms::{observer::SearchObserver, Algorithms},
+    board::{
+        board::{Board, BoardManager},
+        cell::Tile,
+    },
+};
+
+use super::heuristic::HeuristicFn;
+
+/// Open list of one direction, indexed by priority, `f` and `g` so the stopping condition can
+/// read all three minimums.
+#[derive(Default)]
+struct Frontier {
+    open: HashMap<Vec<Tile>, (Board, usize, usize)>,
+    by_priority: BTreeSet<(usize, usize, Vec<Tile>)>,
+    by_f: BTreeSet<(usize, Vec<Tile>)>,
+    by_g: BTreeSet<(usize, Vec<Tile>)>,
+    closed: HashMap<Vec<Tile>, usize>,
+    parents: HashMap<Vec<Tile>, Board>,
+}
+
+impl Frontier {
+    fn with(board: Board, h: usize) -> Self {
+        let mut frontier = Self::default();
+        frontier.push(board, 0, h);
+        frontier
+    }
+
+    /// MM priority: `f`, but never less than twice `g`, so neither side goes past the middle.
+    fn priority(g: usize, h: usize) -> usize {
+        (g + h).max(2 * g)
+    }
+
+    fn push(&mut self, board: Board, g: usize, h: usize) {
+        let tiles = BoardManager::tiles_of(&board).to_vec();
+        self.by_priority
+            .insert((Self::priority(g, h), g, tiles.clone()));
+        self.by_f.insert((g + h, tiles.clone()));
+        self.by_g.insert((g, tiles.clone()));
+        self.open.insert(tiles, (board, g, h));
+    }
+
+    fn remove(&mut self, tiles: &[Tile]) -> Option<(Board, usize, usize)> {
+        let (board, g, h) = self.open.remove(tiles)?;
+        self.by_priority
+            .remove(&(Self::priority(g, h), g, tiles.to_vec()));
+        self.by_f.remove(&(g + h, tiles.to_vec()));
+        self.by_g.remove(&(g, tiles.to_vec()));
+        Some((board, g, h))
+    }
+
+    fn pop(&mut self) -> Option<(Board, usize)> {
+        let (_, _, tiles) = self.by_priority.first()?.clone();
+        let (board, g, _) = self.remove(&tiles)?;
+        self.closed.insert(tiles, g);
+        Some((board, g))
+    }
+
+    /// Cost of the best path known from this side's start to `tiles`.
+    fn g_of(&self, tiles: &[Tile]) -> Option<usize> {
+        self.open
+            .get(tiles)
+            .map(|(_, g, _)| *g)
+            .or_else(|| self.closed.get(tiles).copied())
+    }
+
+    fn min_priority(&self) -> usize {
+        self.by_priority.first().map_or(usize::MAX, |key| key.0)
+    }
+
+    fn min_f(&self) -> usize {
+        self.by_f.first().map_or(usize::MAX, |key| key.0)
+    }
+
+    fn min_g(&self) -> usize {
+        self.by_g.first().map_or(usize::MAX, |key| key.0)
+    }
+
+    /// Boards from this side's start to `board`, start first.
+    fn path_to(&self, board: Board) -> LinkedList<Board> {
+        let mut path = LinkedList::new();
+        let mut current = board;
+        while let Some(parent) = self.parents.get(BoardManager::tiles_of(&current)) {
+            let parent = parent.clone();
+            path.push_front(current);
+            current = parent;
+        }
+        path.push_front(current);
+        path
+    }
+}
+
+/// Bidirectional heuristic search that is guaranteed to meet in the middle (MM).
+///
+/// One A*-like search runs forward from the initial board and one backwards from the goal,
+/// each guided by the heuristic towards the other end through [`HeuristicFn::estimate`]. Nodes
+/// are expanded by `max(f, 2g)`, so neither search expands a node past the midpoint of an
+/// optimal path; the solution is optimal with an admissible heuristic.
+pub struct MmAlgorithms {
+    heuristic: Box<dyn HeuristicFn>,
+}
+
+impl MmAlgorithms {
+    pub fn with(heuristic: Box<dyn HeuristicFn>) -> Self {
+        Self { heuristic }
+    }
+}
+
+impl Algorithms for MmAlgorithms {
+    fn solve_with(
+        &mut self,
+        initial_state: Board,
+        observer: &mut dyn SearchObserver,
+    ) -> Option<LinkedList<Board>> {
+        let start = BoardManager::tiles_of(&initial_state).to_vec();
+        let goal_state = BoardManager::goal_of(&initial_state).to_vec();
+        let goal_board = Board::with_tiles(
+            BoardManager::config_of(&initial_state).clone(),
+            goal_state.clone(),
+        );
+        let forward_h = self.heuristic.estimate(&initial_state, &goal_state);
+        let backward_h = self.heuristic.estimate(&goal_board, &start);
+        // forward searches towards the goal, backward towards the initial board
+        let mut sides = [
+            Frontier::with(initial_state, forward_h),
+            Frontier::with(goal_board, backward_h),
+        ];
+        let targets = [goal_state, start];
+        let mut best: Option<(usize, Board)> = None;
+
+        while !sides[0].open.is_empty() && !sides[1].open.is_empty() {
+            if observer.is_cancelled() {
+                return None;
+            }
+            let priority = sides[0].min_priority().min(sides[1].min_priority());
+            let bound = priority
+                .max(sides[0].min_f())
+                .max(sides[1].min_f())
+                .max(sides[0].min_g().saturating_add(sides[1].min_g()) + 1);
+            if matches!(&best, Some((cost, _)) if *cost <= bound) {
+                break;
+            }
+
+            let side = if sides[0].min_priority() <= sides[1].min_priority() {
+                0
+            } else {
+                1
+            };
+            let (current, g) = sides[side].pop()?;
+            let tiles = BoardManager::tiles_of(&current);
+            if let Some(other) = sides[1 - side].g_of(tiles) {
+                if best.as_ref().is_none_or(|(cost, _)| g + other < *cost) {
+                    best = Some((g + other, current.clone()));
+                }
+            }
+            observer.on_expand(&current);
+            for board in BoardManager::neigbors_of(&current) {
+                let tiles = BoardManager::tiles_of(&board).to_vec();
+                if sides[side].g_of(&tiles).is_some_and(|seen| seen <= g + 1) {
+                    observer.on_duplicate(&board);
+                    continue;
+                }
+                observer.on_generate(&board, &current);
+                sides[side].remove(&tiles);
+                sides[side].closed.remove(&tiles);
+                sides[side].parents.insert(tiles.clone(), current.clone());
+                let h = self.heuristic.estimate(&board, &targets[side]);
+                let board = BoardManager::assign_weight(board, h);
+                if let Some(other) = sides[1 - side].g_of(&tiles) {
+                    if best.as_ref().is_none_or(|(cost, _)| g + 1 + other < *cost) {
+                        best = Some((g + 1 + other, board.clone()));
+                    }
+                }
+                sides[side].push(board, g + 1, h);
+            }
+        }
+
+        let (_, meeting) = best?;
+        let backward = sides[1].path_to(meeting.clone());
+        let mut solutions = sides[0].path_to(meeting);
+        // the backward path runs from the goal to the meeting board, which is already there
+        solutions.extend(backward.into_iter().rev().skip(1));
+        observer.on_solution(&solutions);
+        Some(solutions)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        algorithms::{
+            cancellation::CancelAfter,
+            informed_search::heuristic::mahattan_distance::MahattanDistance,
+        },
+        board::{board::BoardManager, scrambled},
+        AStarAlgorithms, Algorithms, Board, GoalLayout, Scramble, SearchStats,
+    };
+
+    use super::MmAlgorithms;
+
+    #[test]
+    fn it_should_find_optimal_solutions() {
+        for layout in [GoalLayout::RowMajor, GoalLayout::Snail] {
+            for (distance, seed) in [(0, 0), (1, 0), (9, 1), (20, 2), (24, 3)] {
+                let path: Vec<Board> = MmAlgorithms::with(Box::new(MahattanDistance::new()))
+                    .solve(scrambled(3, layout, Scramble::Distance(distance), seed))
+                    .unwrap()
+                    .into_iter()
+                    .collect();
+                assert_eq!(path.len(), distance + 1);
+                assert!(path.last().unwrap().match_goal());
+                assert!(path
+                    .windows(2)
+                    .all(|pair| BoardManager::move_of(&pair[0], &pair[1]).is_some()));
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_compare_expansions_with_a_star() {
+        let board = scrambled(3, GoalLayout::RowMajor, Scramble::Distance(22), 5);
+        let mut mm = SearchStats::new();
+        let mut a_star = SearchStats::new();
+        MmAlgorithms::with(Box::new(MahattanDistance::new()))
+            .solve_with(board.clone(), &mut mm)
+            .unwrap();
+        AStarAlgorithms::with(Box::new(MahattanDistance::new()))
+            .solve_with(board, &mut a_star)
+            .unwrap();
+        assert_eq!(mm.solution_length, a_star.solution_length);
+        // meeting in the middle pays off on this board, as it usually does on deep ones
+        assert!(mm.expanded < a_star.expanded);
+    }
+
+    #[test]
+    fn it_should_stop_when_cancelled_mid_search() {
+        let board = scrambled(4, GoalLayout::RowMajor, Scramble::Uniform, 1);
+        let mut observer = (SearchStats::new(), CancelAfter(100));
+        let path =
+            MmAlgorithms::with(Box::new(MahattanDistance::new())).solve_with(board, &mut observer);
+        assert!(path.is_none());
+        assert_eq!(observer.0.expanded, 100);
+        assert!(observer.0.generated > 0);
+    }
+}
@@ -0,0 +1,299 @@
+use std::{
+    collections::{hash_map::DefaultHasher, BinaryHeap, HashMap, LinkedList},
+    hash::{Hash, Hasher},
+    sync::{
+        atomic::{self, AtomicBool, AtomicUsize},
+        mpsc::{self, Receiver, Sender},
+        Mutex,
+    },
+    thread,
+};
+
+use crate::{
+    algorithms::{observer::SearchObserver, Algorithms},
+    board::{
+        board::{Board, BoardManager},
+        cell::Tile,
+    },
+};
+
+use super::{
+    heuristic::HeuristicFn,
+    parallel::{forward_events, Event, HeuristicFactory, POLL_INTERVAL},
+    search_node::SearchNode,
+};
+
+/// Hash-distributed A*: every state is owned by the worker its tiles hash to, and each worker
+/// keeps its own open and closed lists. Generated states are sent to their owner, so duplicate
+/// detection never needs a lock.
+///
+/// Workers search until no state with `f` below the best solution found so far is left
+/// anywhere, so with an admissible heuristic the solution is optimal, like
+/// [`super::AStarAlgorithms`]. The heuristic is created once per worker by a factory because
+/// heuristics may keep per-search caches.
+pub struct HdaStarAlgorithms {
+    threads: usize,
+    heuristic: HeuristicFactory,
+}
+
+impl HdaStarAlgorithms {
+    pub fn with<F>(threads: usize, heuristic: F) -> Self
+    where
+        F: Fn() -> Box<dyn HeuristicFn> + Send + Sync + 'static,
+    {
+        Self {
+            threads: threads.max(1),
+            heuristic: Box::new(heuristic),
+        }
+    }
+}
+
+impl Algorithms for HdaStarAlgorithms {
+    fn solve_with(
+        &mut self,
+        initial_state: Board,
+        observer: &mut dyn SearchObserver,
+    ) -> Option<LinkedList<Board>> {
+        if observer.is_cancelled() {
+            return None;
+        }
+        let shared = Shared {
+            // the initial state is the only message in flight
+            work: AtomicUsize::new(1),
+            best: AtomicUsize::new(usize::MAX),
+            solution: Mutex::new(None),
+            stop: AtomicBool::new(false),
+        };
+        let (inboxes, receivers): (Vec<Sender<Message>>, Vec<Receiver<Message>>) =
+            (0..self.threads).map(|_| mpsc::channel()).unzip();
+        let (events, event_receiver) = mpsc::channel();
+        inboxes[owner_of(&initial_state, self.threads)]
+            .send(Message {
+                board: initial_state,
+                g: 0,
+                parent: None,
+            })
+            .unwrap();
+
+        let factory = &self.heuristic;
+        let shared = &shared;
+        let closed = thread::scope(|scope| {
+            let handles: Vec<_> = receivers
+                .into_iter()
+                .map(|inbox| {
+                    let outboxes = inboxes.clone();
+                    let events = events.clone();
+                    scope.spawn(move || {
+                        Worker {
+                            heuristic: factory(),
+                            inbox,
+                            outboxes,
+                            events,
+                            shared,
+                            open: BinaryHeap::new(),
+                            pushed: 0,
+                            closed: HashMap::new(),
+                        }
+                        .run()
+                    })
+                })
+                .collect();
+            drop(events);
+            drop(inboxes);
+
+            forward_events(event_receiver, observer, &shared.stop);
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect::<HashMap<Vec<Tile>, (usize, Option<Board>)>>()
+        });
+
+        if observer.is_cancelled() {
+            return None;
+        }
+        let mut current = shared.solution.lock().unwrap().take()?;
+        let mut solutions = LinkedList::new();
+        solutions.push_front(current.clone());
+        while let Some((_, Some(parent))) = closed.get(BoardManager::tiles_of(&current)) {
+            solutions.push_front(parent.clone());
+            current = parent.clone();
+        }
+        observer.on_solution(&solutions);
+        Some(solutions)
+    }
+}
+
+fn owner_of(board: &Board, threads: usize) -> usize {
+    let mut hasher = DefaultHasher::new();
+    board.hash(&mut hasher);
+    (hasher.finish() % threads as u64) as usize
+}
+
+struct Shared {
+    /// Messages in flight plus states waiting in open lists; the search is over at zero.
+    work: AtomicUsize,
+    /// Cost of the best solution found so far.
+    best: AtomicUsize,
+    solution: Mutex<Option<Board>>,
+    stop: AtomicBool,
+}
+
+struct Message {
+    board: Board,
+    g: usize,
+    parent: Option<Board>,
+}
+
+struct Worker<'a> {
+    heuristic: Box<dyn HeuristicFn>,
+    inbox: Receiver<Message>,
+    outboxes: Vec<Sender<Message>>,
+    events: Sender<Event>,
+    shared: &'a Shared,
+    open: BinaryHeap<SearchNode>,
+    pushed: usize,
+    closed: HashMap<Vec<Tile>, (usize, Option<Board>)>,
+}
+
+impl Worker<'_> {
+    fn run(mut self) -> HashMap<Vec<Tile>, (usize, Option<Board>)> {
+        while !self.shared.stop.load(atomic::Ordering::Relaxed) {
+            while let Ok(message) = self.inbox.try_recv() {
+                self.receive(message);
+            }
+            if let Some(node) = self.open.pop() {
+                self.expand(node);
+                self.shared.work.fetch_sub(1, atomic::Ordering::SeqCst);
+                continue;
+            }
+            if self.shared.work.load(atomic::Ordering::SeqCst) == 0 {
+                break;
+            }
+            if let Ok(message) = self.inbox.recv_timeout(POLL_INTERVAL) {
+                self.receive(message);
+            }
+        }
+        self.closed
+    }
+
+    fn receive(&mut self, message: Message) {
+        let Message { board, g, parent } = message;
+        let tiles = BoardManager::tiles_of(&board).to_vec();
+        let h = self.heuristic.compute(&board, None);
+        let is_duplicate = matches!(self.closed.get(&tiles), Some((seen, _)) if *seen <= g);
+        if is_duplicate || g + h >= self.shared.best.load(atomic::Ordering::SeqCst) {
+            let _ = self.events.send(Event::Duplicate(board));
+            self.shared.work.fetch_sub(1, atomic::Ordering::SeqCst);
+            return;
+        }
+        self.closed.insert(tiles, (g, parent));
+        self.open.push(SearchNode::new(
+            BoardManager::assign_weight(board, h),
+            g,
+            h,
+            self.pushed,
+        ));
+        self.pushed += 1;
+    }
+
+    fn expand(&mut self, node: SearchNode) {
+        let (f, g) = (node.f(), node.g());
+        let board = node.into_board();
+        let stale = matches!(
+            self.closed.get(BoardManager::tiles_of(&board)),
+            Some((seen, _)) if *seen < g
+        );
+        if stale || f >= self.shared.best.load(atomic::Ordering::SeqCst) {
+            return;
+        }
+        if board.match_goal() {
+            let mut solution = self.shared.solution.lock().unwrap();
+            if g < self.shared.best.load(atomic::Ordering::SeqCst) {
+                self.shared.best.store(g, atomic::Ordering::SeqCst);
+                *solution = Some(board);
+            }
+            return;
+        }
+        let children = BoardManager::neigbors_of(&board);
+        self.shared
+            .work
+            .fetch_add(children.len(), atomic::Ordering::SeqCst);
+        children.iter().for_each(|child| {
+            let _ = self.outboxes[owner_of(child, self.outboxes.len())].send(Message {
+                board: child.clone(),
+                g: g + 1,
+                parent: Some(board.clone()),
+            });
+        });
+        let _ = self.events.send(Event::Expand(board, children));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        algorithms::{
+            cancellation::CancelAfter,
+            informed_search::heuristic::{
+                inversion_distance::InversionDistance, mahattan_distance::MahattanDistance,
+            },
+        },
+        board::{board::BoardManager, scrambled},
+        AStarAlgorithms, Algorithms, Board, GoalLayout, Scramble, SearchStats,
+    };
+
+    use super::HdaStarAlgorithms;
+
+    #[test]
+    fn it_should_find_optimal_solutions() {
+        for threads in [1, 4] {
+            for seed in 0..3 {
+                let board = scrambled(3, GoalLayout::RowMajor, Scramble::Distance(14), seed);
+                let mut solver =
+                    HdaStarAlgorithms::with(threads, || Box::new(MahattanDistance::new()));
+                let path: Vec<Board> = solver.solve(board).unwrap().into_iter().collect();
+                assert_eq!(path.len(), 15);
+                assert!(path.last().unwrap().match_goal());
+                assert!(path
+                    .windows(2)
+                    .all(|pair| BoardManager::move_of(&pair[0], &pair[1]).is_some()));
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_match_a_star_path_lengths() {
+        let board = scrambled(4, GoalLayout::RowMajor, Scramble::Walk(40), 7);
+        let expected = AStarAlgorithms::with(Box::new(MahattanDistance::new()))
+            .solve(board.clone())
+            .unwrap();
+        let mut stats = SearchStats::new();
+        let path = HdaStarAlgorithms::with(4, || Box::new(MahattanDistance::new()))
+            .solve_with(board, &mut stats)
+            .unwrap();
+        assert_eq!(path.len(), expected.len());
+        assert_eq!(stats.solution_length, Some(expected.len() - 1));
+        assert!(stats.expanded > 0);
+    }
+
+    #[test]
+    fn it_should_create_a_heuristic_per_worker() {
+        let board = scrambled(3, GoalLayout::RowMajor, Scramble::Distance(6), 1);
+        let path = HdaStarAlgorithms::with(3, || Box::new(InversionDistance::new()))
+            .solve(board)
+            .unwrap();
+        assert!(path.back().unwrap().match_goal());
+    }
+
+    #[test]
+    fn it_should_stop_when_cancelled_mid_search() {
+        let board = scrambled(4, GoalLayout::RowMajor, Scramble::Uniform, 1);
+        let mut observer = (SearchStats::new(), CancelAfter(100));
+        let path = HdaStarAlgorithms::with(2, || Box::new(MahattanDistance::new()))
+            .solve_with(board, &mut observer);
+        assert!(path.is_none());
+        assert!(observer.0.expanded >= 100);
+        assert!(observer.0.generated > 0);
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{board::board::BoardManager, Board};
+
+use super::{goal_positions, HeuristicFn};
+
+/// Gaschnig's distance (n-MaxSwap): the moves needed were the empty tile allowed to swap with
+/// any tile, not only its neighbours.
+///
+/// While the empty tile is away from its goal cell it swaps with the tile that belongs there;
+/// once it is home while tiles are still misplaced, it swaps with any of them to start over.
+pub fn gaschnig_distance(board: &Board) -> usize {
+    let goal = goal_positions(board);
+    // value whose goal is every cell, and cell of every value as the swaps go
+    let mut owners = vec![0; goal.len()];
+    let mut cells = vec![0; goal.len()];
+    for (value, goal_idx) in goal.iter().enumerate() {
+        owners[*goal_idx] = value;
+    }
+    for (idx, tile) in BoardManager::tiles_of(board).iter().enumerate() {
+        cells[tile.get_value() as usize] = idx;
+    }
+
+    let mut swaps = 0;
+    loop {
+        let blank = cells[0];
+        let value = if blank != goal[0] {
+            owners[blank]
+        } else {
+            match (1..goal.len()).find(|value| cells[*value] != goal[*value]) {
+                Some(value) => value,
+                None => return swaps,
+            }
+        };
+        cells[0] = cells[value];
+        cells[value] = blank;
+        swaps += 1;
+    }
+}
+
+pub struct GaschnigDistance;
+
+impl HeuristicFn for GaschnigDistance {
+    fn compute(&self, new_state: &Board, _old_state: Option<&Board>) -> usize {
+        gaschnig_distance(new_state)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        algorithms::informed_search::heuristic::{
+            misplaced_tiles::misplaced_tiles, perfect::PerfectHeuristic,
+        },
+        board::{parser::BoardParser, scrambled},
+        GoalLayout, Scramble,
+    };
+
+    use super::gaschnig_distance;
+
+    #[test]
+    fn it_should_count_swaps_with_the_empty_tile() {
+        let board = BoardParser::parse("1 2 3 4 5 6 7 8 0", true).unwrap();
+        assert_eq!(gaschnig_distance(&board), 0);
+        // the empty tile brings 5 home, then swaps with 1 to open the cycle of 1, 2, 6 and 8
+        let board = BoardParser::parse("8 1 3 4 0 2 7 6 5", true).unwrap();
+        assert_eq!(gaschnig_distance(&board), 6);
+        // both cycles have to be opened by the empty tile, which starts at home
+        let board = BoardParser::parse("2 1 3 4 5 6 8 7 0", false).unwrap();
+        assert_eq!(gaschnig_distance(&board), 6);
+    }
+
+    #[test]
+    fn it_should_lie_between_hamming_and_the_optimal_length() {
+        let perfect = PerfectHeuristic::new();
+        for layout in [GoalLayout::RowMajor, GoalLayout::BlankFirst] {
+            for seed in 0..20 {
+                let board = scrambled(3, layout, Scramble::Walk(60), seed);
+                let gaschnig = gaschnig_distance(&board);
+                assert!(gaschnig >= misplaced_tiles(&board));
+                assert!(gaschnig <= perfect.distance(&board).unwrap());
+            }
+        }
+    }
+}
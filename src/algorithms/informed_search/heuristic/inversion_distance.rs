@@ -21,6 +21,7 @@ impl InversionDistance {
             cache: RefCell::new(HashMap::new()),
         }
     }
+
     fn transpose(&self, tiles: &[Tile], n: usize) -> Vec<Tile> {
         let mut result = Vec::new();
 
@@ -49,11 +50,11 @@ impl InversionDistance {
                 continue;
             }
             let order_i = order.get(&tiles[i].get_value()).unwrap();
-            for j in (i + 1)..tiles.len() {
-                if tiles[j].get_value() == 0 {
+            for tile in tiles.iter().skip(i + 1) {
+                if tile.get_value() == 0 {
                     continue;
                 }
-                let order_j = order.get(&tiles[j].get_value()).unwrap();
+                let order_j = order.get(&tile.get_value()).unwrap();
                 if order_i > order_j {
                     count += 1;
                 }
@@ -76,9 +77,14 @@ impl InversionDistance {
     }
 }
 
+impl Default for InversionDistance {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl HeuristicFn for InversionDistance {
     fn compute(&self, new_state: &Board, old_state: Option<&Board>) -> usize {
-        // Still bugs
         match old_state {
             None => {
                 let horizontal = self.inversion_count(new_state, false);
@@ -92,10 +98,8 @@ impl HeuristicFn for InversionDistance {
                     .borrow_mut()
                     .insert(tiles, (horizontal, vertical));
 
-                let heuristic = (horizontal / (n - 1) + horizontal % (n - 1))
-                    + (vertical / (n - 1) + vertical % (n - 1));
-
-                heuristic
+                (horizontal / (n - 1) + horizontal % (n - 1))
+                    + (vertical / (n - 1) + vertical % (n - 1))
             }
             Some(old_state) => {
                 let mut old_idx = BoardManager::empty_tile_idx(old_state) as usize;
@@ -124,10 +128,13 @@ impl HeuristicFn for InversionDistance {
                 let start = min(old_idx, new_idx);
                 let end = max(old_idx, new_idx);
 
+                // every tile the swapped one jumps over flips whether the pair is inverted
                 let swapped_tile_order = order.get(&new_tiles[old_idx].get_value()).unwrap();
-                for i in (start + 1)..end {
-                    if order.get(&new_tiles[i].get_value()).unwrap() < swapped_tile_order {
+                for tile in new_tiles.iter().take(end).skip(start + 1) {
+                    if order.get(&tile.get_value()).unwrap() < swapped_tile_order {
                         change += 1;
+                    } else {
+                        change -= 1;
                     }
                 }
 
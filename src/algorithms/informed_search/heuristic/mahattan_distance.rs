@@ -1,9 +1,14 @@
 use crate::{board::board::BoardManager, Board};
 
-use super::HeuristicFn;
+use super::{goal_positions, GoalPositions, HeuristicFn};
+
+fn distance(from: usize, to: usize, n: usize) -> usize {
+    (from % n).abs_diff(to % n) + (from / n).abs_diff(to / n)
+}
 
 pub fn mahattan_distance(board: &Board, _old_idx: Option<usize>) -> usize {
     let n = BoardManager::size_of(board) as usize;
+    let positions = goal_positions(board);
     BoardManager::tiles_of(board)
         .iter()
         .enumerate()
@@ -12,44 +17,80 @@ pub fn mahattan_distance(board: &Board, _old_idx: Option<usize>) -> usize {
             if value == 0 {
                 return 0;
             }
-            let dx = ((value - 1) % n).abs_diff(idx % n);
-            let dy = (value / n).abs_diff(idx / n);
-            dx + dy
+            distance(idx, positions[value], n)
         })
-        .reduce(|acc, value| acc + value)
-        .unwrap()
+        .sum()
 }
 
-pub struct MahattanDistance;
+#[derive(Default)]
+pub struct MahattanDistance {
+    goal: GoalPositions,
+}
+
+impl MahattanDistance {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
 
 impl HeuristicFn for MahattanDistance {
     fn compute(&self, new_state: &Board, old_state: Option<&Board>) -> usize {
-        let n = BoardManager::size_of(new_state) as usize;
-        let tiles = BoardManager::tiles_of(new_state);
         match old_state {
-            None => tiles
-                .iter()
-                .enumerate()
-                .map(|(idx, tile)| {
-                    let value = tile.get_value() as usize;
-                    if value == 0 {
-                        return 0;
-                    }
-                    let dx = ((value - 1) % n).abs_diff(idx % n);
-                    let dy = (value / n).abs_diff(idx / n);
-                    dx + dy
-                })
-                .reduce(|acc, value| acc + value)
-                .unwrap(),
-            Some(old_state) => {
+            // uninformed boards do not carry the old value to update
+            Some(old_state) if BoardManager::is_informed(old_state) => {
+                let n = BoardManager::size_of(new_state) as usize;
                 let old_idx = BoardManager::empty_tile_idx(old_state) as usize;
+                let new_idx = BoardManager::empty_tile_idx(new_state) as usize;
                 let moved_tile_value =
                     BoardManager::tiles_of(new_state)[old_idx].get_value() as usize;
-                let dx = ((moved_tile_value - 1) % n).abs_diff(old_idx % n);
-                let dy = (moved_tile_value / n).abs_diff(old_idx / n);
-                let change = dx + dy;
-                BoardManager::heuristic_value_of(new_state) + change
+                let goal_idx = self.goal.of(new_state)[moved_tile_value];
+                BoardManager::heuristic_value_of(old_state)
+                    .checked_sub(distance(new_idx, goal_idx, n))
+                    .map_or_else(
+                        || mahattan_distance(new_state, None),
+                        |value| value + distance(old_idx, goal_idx, n),
+                    )
             }
+            _ => mahattan_distance(new_state, None),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        algorithms::informed_search::heuristic::HeuristicFn,
+        board::{board::BoardManager, parser::BoardParser},
+        GoalLayout,
+    };
+
+    use super::{mahattan_distance, MahattanDistance};
+
+    #[test]
+    fn it_should_measure_distances_to_the_goal() {
+        let board = BoardParser::parse("1 2 3 4 5 6 7 8 0", true).unwrap();
+        assert_eq!(mahattan_distance(&board, None), 0);
+        let board = BoardParser::parse("8 1 3 4 0 2 7 6 5", true).unwrap();
+        assert_eq!(mahattan_distance(&board, None), 10);
+        let board =
+            BoardParser::parse_with_layout("1 2 3 4 5 6 7 8 0", GoalLayout::BlankFirst, true)
+                .unwrap();
+        assert_eq!(mahattan_distance(&board, None), 12);
+    }
+
+    #[test]
+    fn it_should_recompute_when_the_old_value_is_too_small_to_update() {
+        let heuristic = MahattanDistance::new();
+        // a weight given by another heuristic, as when boards are shared between searches
+        let board = BoardParser::parse("8 1 3 4 0 2 7 6 5", true).unwrap();
+        let board = BoardManager::assign_weight(board, 0);
+        let mut neighbors = BoardManager::neigbors_of(&board).into_iter();
+        // the 2 slides towards its goal, so the update would take 2 off a weight of 0
+        let neighbor = neighbors.find(|neighbor| BoardManager::empty_tile_idx(neighbor) == 5);
+        let neighbor = neighbor.unwrap();
+        assert_eq!(
+            heuristic.compute(&neighbor, Some(&board)),
+            mahattan_distance(&neighbor, None)
+        );
+    }
+}
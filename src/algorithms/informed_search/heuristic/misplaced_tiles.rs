@@ -0,0 +1,47 @@
+use crate::{board::board::BoardManager, Board};
+
+use super::HeuristicFn;
+
+/// Hamming distance: the number of tiles, the empty one aside, that are not on their goal cell.
+pub fn misplaced_tiles(board: &Board) -> usize {
+    BoardManager::tiles_of(board)
+        .iter()
+        .zip(BoardManager::goal_of(board))
+        .filter(|(tile, goal)| tile.get_value() != 0 && tile != goal)
+        .count()
+}
+
+pub struct MisplacedTiles;
+
+impl HeuristicFn for MisplacedTiles {
+    fn compute(&self, new_state: &Board, old_state: Option<&Board>) -> usize {
+        match old_state {
+            Some(old_state) if BoardManager::is_informed(old_state) => {
+                let old_idx = BoardManager::empty_tile_idx(old_state) as usize;
+                let new_idx = BoardManager::empty_tile_idx(new_state) as usize;
+                let goal = BoardManager::goal_of(new_state);
+                let moved_tile = &BoardManager::tiles_of(new_state)[old_idx];
+                (BoardManager::heuristic_value_of(old_state)
+                    + (goal[old_idx] != *moved_tile) as usize)
+                    .checked_sub((goal[new_idx] != *moved_tile) as usize)
+                    .unwrap_or_else(|| misplaced_tiles(new_state))
+            }
+            _ => misplaced_tiles(new_state),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::board::parser::BoardParser;
+
+    use super::misplaced_tiles;
+
+    #[test]
+    fn it_should_count_misplaced_tiles() {
+        let board = BoardParser::parse("1 2 3 4 5 6 7 8 0", true).unwrap();
+        assert_eq!(misplaced_tiles(&board), 0);
+        let board = BoardParser::parse("8 1 3 4 0 2 7 6 5", true).unwrap();
+        assert_eq!(misplaced_tiles(&board), 5);
+    }
+}
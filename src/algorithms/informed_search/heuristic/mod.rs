@@ -1,9 +1,123 @@
-use crate::Board;
+use std::cell::{Ref, RefCell};
 
+use crate::{
+    board::{board::BoardManager, cell::Tile},
+    Board,
+};
+
+pub mod gaschnig;
 pub mod inversion_distance;
 pub mod mahattan_distance;
+pub mod misplaced_tiles;
+pub mod out_of_line;
 pub mod pattern_database;
+pub mod perfect;
+pub mod walking_distance;
 
 pub trait HeuristicFn {
-    fn compute(&self, new_state: &Board, old_idx: Option<&Board>) -> usize;
+    /// Estimates the moves from `new_state` to its goal.
+    ///
+    /// `old_state` is the board `new_state` was generated from, if any, weighted with the value
+    /// this heuristic gave it. A move only takes the tile that slid into the old empty cell to
+    /// another cell, so heuristics made of one term per tile update the old value by that
+    /// tile's term rather than starting over, and start over whenever the old value cannot be
+    /// theirs.
+    fn compute(&self, new_state: &Board, old_state: Option<&Board>) -> usize;
+
+    /// Estimates the moves from `state` to `target` rather than to the goal of `state`, as
+    /// searches running backwards from the goal need.
+    fn estimate(&self, state: &Board, target: &[Tile]) -> usize {
+        self.compute(&BoardManager::retarget(state, target), None)
+    }
+}
+
+/// Goal index of every tile value, so distances are measured against the board's own goal.
+pub(super) fn goal_positions(board: &Board) -> Vec<usize> {
+    let goal = BoardManager::goal_of(board);
+    let mut positions = vec![0; goal.len()];
+    goal.iter()
+        .enumerate()
+        .for_each(|(idx, tile)| positions[tile.get_value() as usize] = idx);
+    positions
+}
+
+/// [`goal_positions`] of the last goal seen, so heuristics updating a value move by move do
+/// not scan the goal on every call.
+#[derive(Default)]
+pub(super) struct GoalPositions {
+    cached: RefCell<Option<(Vec<Tile>, Vec<usize>)>>,
+}
+
+impl GoalPositions {
+    pub(super) fn of(&self, board: &Board) -> Ref<'_, [usize]> {
+        let goal = BoardManager::goal_of(board);
+        if self
+            .cached
+            .borrow()
+            .as_ref()
+            .is_none_or(|(cached_goal, _)| cached_goal != goal)
+        {
+            *self.cached.borrow_mut() = Some((goal.to_vec(), goal_positions(board)));
+        }
+        Ref::map(self.cached.borrow(), |cached| {
+            cached.as_ref().map_or(&[][..], |(_, positions)| positions)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        board::{board::BoardManager, scrambled},
+        GoalLayout, Scramble,
+    };
+
+    use super::{
+        gaschnig::GaschnigDistance, inversion_distance::InversionDistance,
+        mahattan_distance::MahattanDistance, misplaced_tiles::MisplacedTiles,
+        out_of_line::OutOfRowAndColumn, perfect::PerfectHeuristic,
+        walking_distance::WalkingDistance, HeuristicFn,
+    };
+
+    type Factory = fn() -> Box<dyn HeuristicFn>;
+
+    const HEURISTICS: [(&str, Factory); 7] = [
+        ("misplaced tiles", || Box::new(MisplacedTiles)),
+        ("gaschnig", || Box::new(GaschnigDistance)),
+        ("out of row and column", || {
+            Box::new(OutOfRowAndColumn::new())
+        }),
+        ("manhattan", || Box::new(MahattanDistance::new())),
+        ("inversion", || Box::new(InversionDistance::new())),
+        ("perfect", || Box::new(PerfectHeuristic::new())),
+        ("walking", || Box::new(WalkingDistance::new())),
+    ];
+
+    #[test]
+    fn it_should_update_incrementally() {
+        for (n, layout) in [
+            (3, GoalLayout::RowMajor),
+            (3, GoalLayout::Snail),
+            (4, GoalLayout::Snail),
+        ] {
+            for (name, heuristic) in HEURISTICS {
+                let (incremental, full) = (heuristic(), heuristic());
+                let board = scrambled(n, layout, Scramble::Walk(60), n as u64);
+                let weight = incremental.compute(&board, None);
+                let mut board = BoardManager::assign_weight(board, weight);
+                for step in 0..30 {
+                    let neighbors = BoardManager::neigbors_of(&board);
+                    let weights: Vec<usize> = neighbors
+                        .iter()
+                        .map(|neighbor| incremental.compute(neighbor, Some(&board)))
+                        .collect();
+                    for (neighbor, weight) in neighbors.iter().zip(&weights) {
+                        assert_eq!(*weight, full.compute(neighbor, None), "{} {}", name, layout);
+                    }
+                    let next = step % neighbors.len();
+                    board = BoardManager::assign_weight(neighbors[next].clone(), weights[next]);
+                }
+            }
+        }
+    }
 }
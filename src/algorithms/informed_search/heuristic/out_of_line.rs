@@ -0,0 +1,71 @@
+use crate::{board::board::BoardManager, Board};
+
+use super::{goal_positions, GoalPositions, HeuristicFn};
+
+/// Whether a tile at `idx` is out of its goal row plus whether it is out of its goal column.
+fn out_of_line(idx: usize, goal_idx: usize, n: usize) -> usize {
+    (idx / n != goal_idx / n) as usize + (idx % n != goal_idx % n) as usize
+}
+
+/// Tiles out of their goal row plus tiles out of their goal column, the empty tile aside.
+pub fn out_of_row_and_column(board: &Board) -> usize {
+    let n = BoardManager::size_of(board) as usize;
+    let positions = goal_positions(board);
+    BoardManager::tiles_of(board)
+        .iter()
+        .enumerate()
+        .map(|(idx, tile)| match tile.get_value() as usize {
+            0 => 0,
+            value => out_of_line(idx, positions[value], n),
+        })
+        .sum()
+}
+
+#[derive(Default)]
+pub struct OutOfRowAndColumn {
+    goal: GoalPositions,
+}
+
+impl OutOfRowAndColumn {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl HeuristicFn for OutOfRowAndColumn {
+    fn compute(&self, new_state: &Board, old_state: Option<&Board>) -> usize {
+        match old_state {
+            Some(old_state) if BoardManager::is_informed(old_state) => {
+                let n = BoardManager::size_of(new_state) as usize;
+                let old_idx = BoardManager::empty_tile_idx(old_state) as usize;
+                let new_idx = BoardManager::empty_tile_idx(new_state) as usize;
+                let moved_tile_value =
+                    BoardManager::tiles_of(new_state)[old_idx].get_value() as usize;
+                let goal_idx = self.goal.of(new_state)[moved_tile_value];
+                BoardManager::heuristic_value_of(old_state)
+                    .checked_sub(out_of_line(new_idx, goal_idx, n))
+                    .map_or_else(
+                        || out_of_row_and_column(new_state),
+                        |value| value + out_of_line(old_idx, goal_idx, n),
+                    )
+            }
+            _ => out_of_row_and_column(new_state),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::board::parser::BoardParser;
+
+    use super::out_of_row_and_column;
+
+    #[test]
+    fn it_should_count_tiles_out_of_their_lines() {
+        let board = BoardParser::parse("1 2 3 4 5 6 7 8 0", true).unwrap();
+        assert_eq!(out_of_row_and_column(&board), 0);
+        // 8, 2, 6 and 5 are out of both, 1 only out of its column
+        let board = BoardParser::parse("8 1 3 4 0 2 7 6 5", true).unwrap();
+        assert_eq!(out_of_row_and_column(&board), 9);
+    }
+}
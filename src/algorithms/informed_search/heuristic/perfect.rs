@@ -0,0 +1,131 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    sync::{Arc, Mutex, OnceLock},
+};
+
+use crate::{
+    algorithms::state_space::StateSpace,
+    board::{board::BoardManager, cell::Tile},
+    Board,
+};
+
+use super::{mahattan_distance::mahattan_distance, HeuristicFn};
+
+/// Tables already explored, one per goal, shared by every [`PerfectHeuristic`] of the process
+/// since exploring one takes a full breadth-first search of the 8-puzzle. Instances only lock
+/// it the first time they meet a goal.
+fn tables() -> &'static Mutex<HashMap<Vec<Tile>, Arc<StateSpace>>> {
+    static TABLES: OnceLock<Mutex<HashMap<Vec<Tile>, Arc<StateSpace>>>> = OnceLock::new();
+    TABLES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// The exact number of moves to the goal of a 3x3 board, looked up in the distance table of
+/// its goal's [`StateSpace`].
+///
+/// A* guided by it expands nothing but the solution path, which makes it a ground truth to
+/// check solvers against. Each goal's table is explored the first time a board with that goal
+/// is seen. Boards of other sizes cannot be looked up and get their Manhattan distance.
+#[derive(Default)]
+pub struct PerfectHeuristic {
+    /// Table of the last goal looked up, so searches, which keep to one goal, never lock.
+    table: RefCell<Option<(Vec<Tile>, Arc<StateSpace>)>>,
+}
+
+impl PerfectHeuristic {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn table(goal: &[Tile]) -> Option<Arc<StateSpace>> {
+        let mut tables = tables().lock().unwrap();
+        if let Some(table) = tables.get(goal) {
+            return Some(table.clone());
+        }
+        let table = Arc::new(StateSpace::explore_from(3, 3, goal).ok()?);
+        tables.insert(goal.to_vec(), table.clone());
+        Some(table)
+    }
+
+    /// Optimal solution length of `board`, `None` if it is not a 3x3 board that can reach its
+    /// goal.
+    pub fn distance(&self, board: &Board) -> Option<usize> {
+        if BoardManager::size_of(board) != 3 {
+            return None;
+        }
+        let goal = BoardManager::goal_of(board);
+        let mut cached = self.table.borrow_mut();
+        if cached
+            .as_ref()
+            .is_none_or(|(cached_goal, _)| cached_goal != goal)
+        {
+            *cached = Some((goal.to_vec(), Self::table(goal)?));
+        }
+        let (_, table) = cached.as_ref()?;
+        table.distance_of(BoardManager::tiles_of(board))
+    }
+}
+
+impl HeuristicFn for PerfectHeuristic {
+    fn compute(&self, new_state: &Board, _old_state: Option<&Board>) -> usize {
+        self.distance(new_state)
+            .unwrap_or_else(|| mahattan_distance(new_state, None))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        algorithms::informed_search::heuristic::HeuristicFn,
+        board::{board::BoardManager, parser::BoardParser, scrambled},
+        AStarAlgorithms, Algorithms, Board, GoalLayout, OptimalSolutions, Scramble, SearchObserver,
+    };
+
+    use super::PerfectHeuristic;
+
+    #[test]
+    fn it_should_give_exact_distances() {
+        let heuristic = PerfectHeuristic::new();
+        for layout in [GoalLayout::RowMajor, GoalLayout::Snail] {
+            for (distance, seed) in [(0, 0), (7, 1), (18, 2), (25, 3)] {
+                let board = scrambled(3, layout, Scramble::Distance(distance), seed);
+                assert_eq!(heuristic.distance(&board), Some(distance));
+                assert_eq!(heuristic.compute(&board, None), distance);
+                assert_eq!(
+                    OptimalSolutions::search(&board).map(|solutions| solutions.length()),
+                    Some(distance)
+                );
+            }
+        }
+    }
+
+    /// Costs of the boards A* expands.
+    #[derive(Default)]
+    struct Expanded(Vec<usize>);
+
+    impl SearchObserver for Expanded {
+        fn on_expand(&mut self, board: &Board) {
+            self.0.push(BoardManager::total_weight(board));
+        }
+    }
+
+    #[test]
+    fn it_should_lead_a_star_straight_to_the_goal() {
+        let board = scrambled(3, GoalLayout::RowMajor, Scramble::Distance(24), 4);
+        let mut expanded = Expanded::default();
+        let path = AStarAlgorithms::with(Box::new(PerfectHeuristic::new()))
+            .solve_with(board, &mut expanded)
+            .unwrap();
+        assert_eq!(path.len(), 25);
+        // ties go to the deepest board, so only the boards before the goal are expanded
+        assert_eq!(expanded.0, vec![24; 24]);
+    }
+
+    #[test]
+    fn it_should_fall_back_on_other_sizes() {
+        let board = BoardParser::parse("1 2 3 4 5 6 7 8 9 10 11 12 13 14 0 15", true).unwrap();
+        let heuristic = PerfectHeuristic::new();
+        assert_eq!(heuristic.distance(&board), None);
+        assert_eq!(heuristic.compute(&board, None), 1);
+    }
+}
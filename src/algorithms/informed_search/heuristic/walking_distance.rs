@@ -0,0 +1,298 @@
+use std::{
+    cell::RefCell,
+    collections::{hash_map::Entry, HashMap, VecDeque},
+    sync::OnceLock,
+};
+
+use crate::{
+    board::{board::BoardManager, cell::Tile},
+    Board,
+};
+
+use super::{mahattan_distance::mahattan_distance, GoalPositions, HeuristicFn};
+
+/// Largest board the occupancy matrices can be packed for: 16 counts of 3 bits each.
+const MAX_SIZE: usize = 4;
+
+/// Occupancy of the lines of one axis: how many tiles of each goal line every line holds,
+/// `counts[line * n + goal_line]`, along with the line of the empty tile.
+#[derive(Clone)]
+struct Occupancy {
+    counts: Vec<u8>,
+    blank: usize,
+}
+
+impl Occupancy {
+    fn key(&self) -> u64 {
+        self.counts
+            .iter()
+            .fold(self.blank as u64, |key, count| key << 3 | *count as u64)
+    }
+}
+
+type Table = HashMap<u64, u8>;
+
+/// Table of the board size `n` and goal line `blank` of the empty tile, explored the first time
+/// it is needed; the same table serves rows and columns. Each table is set once, so reading it
+/// takes no lock.
+fn table(n: usize, blank: usize) -> &'static Table {
+    static TABLES: [[OnceLock<Table>; MAX_SIZE]; MAX_SIZE + 1] =
+        [const { [const { OnceLock::new() }; MAX_SIZE] }; MAX_SIZE + 1];
+    TABLES[n][blank].get_or_init(|| explore(n, blank))
+}
+
+/// Explores every occupancy reachable from the goal one, where each line holds its own `n`
+/// tiles but the line of the empty tile, which holds `n - 1`.
+fn explore(n: usize, blank: usize) -> Table {
+    let mut goal = Occupancy {
+        counts: vec![0; n * n],
+        blank,
+    };
+    for line in 0..n {
+        goal.counts[line * n + line] = (if line == blank { n - 1 } else { n }) as u8;
+    }
+    let mut table = HashMap::from([(goal.key(), 0)]);
+    let mut queue = VecDeque::from([goal]);
+    while let Some(state) = queue.pop_front() {
+        let distance = table[&state.key()];
+        let lines = [state.blank.checked_sub(1), Some(state.blank + 1)];
+        for line in lines.into_iter().flatten().filter(|line| *line < n) {
+            // any tile of the neighbouring line can slide into the empty tile's line
+            for goal_line in 0..n {
+                if state.counts[line * n + goal_line] == 0 {
+                    continue;
+                }
+                let mut next = state.clone();
+                next.counts[line * n + goal_line] -= 1;
+                next.counts[state.blank * n + goal_line] += 1;
+                next.blank = line;
+                if let Entry::Vacant(entry) = table.entry(next.key()) {
+                    entry.insert(distance + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+    table
+}
+
+/// Occupancy key of one axis of a board along with its walking distance.
+#[derive(Clone, Copy)]
+struct Axis {
+    key: u64,
+    distance: u8,
+}
+
+impl Axis {
+    fn of(n: usize, occupancy: &Occupancy, goal_blank: usize) -> Self {
+        let key = occupancy.key();
+        // every occupancy is reachable since tiles of a line can be rearranged at will
+        let distance = table(n, goal_blank).get(&key).copied().unwrap_or(0);
+        Self { key, distance }
+    }
+
+    /// The axis once a tile of `goal_line` slid from line `from` into the empty tile's line
+    /// `to`, which leaves the empty tile on `from`. Only two counts of the key change.
+    fn slide(
+        self,
+        n: usize,
+        goal_line: usize,
+        from: usize,
+        to: usize,
+        goal_blank: usize,
+    ) -> Option<Self> {
+        let shift = |line: usize| 3 * (n * n - 1 - (line * n + goal_line));
+        let counts = self.key & ((1 << (3 * n * n)) - 1);
+        let counts = counts.checked_sub(1 << shift(from))? + (1 << shift(to));
+        let key = (from as u64) << (3 * n * n) | counts;
+        let distance = *table(n, goal_blank).get(&key)?;
+        Some(Self { key, distance })
+    }
+}
+
+/// Axes of the board whose children are being estimated, measured against `goal`.
+struct Parent {
+    tiles: Vec<Tile>,
+    goal: Vec<Tile>,
+    rows: Axis,
+    columns: Axis,
+}
+
+/// Walking distance: the moves needed to bring every tile back to its goal row, were tiles of
+/// the same row interchangeable, plus the same for columns.
+///
+/// Unlike Manhattan distance it accounts for tiles of one row having to queue up through the
+/// single empty cell, so it is considerably stronger while staying admissible. The row and
+/// column tables are explored by a breadth-first search the first time a board size and goal
+/// blank line are met, and shared by every instance. Boards larger than 4x4 get their
+/// Manhattan distance.
+#[derive(Default)]
+pub struct WalkingDistance {
+    goal: GoalPositions,
+    /// Searches estimate every child of a board in a row, so the children only build the axes
+    /// of their parent once and each updates the one axis its move runs along.
+    parent: RefCell<Option<Parent>>,
+}
+
+impl WalkingDistance {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Walking distance along rows, or along columns when `rows` is false.
+    fn axis(board: &Board, rows: bool) -> Axis {
+        let n = BoardManager::size_of(board) as usize;
+        let line = |idx: usize| if rows { idx / n } else { idx % n };
+        let goal = BoardManager::goal_of(board);
+        let mut goal_lines = vec![0; goal.len()];
+        goal.iter()
+            .enumerate()
+            .for_each(|(idx, tile)| goal_lines[tile.get_value() as usize] = line(idx));
+
+        let mut occupancy = Occupancy {
+            counts: vec![0; n * n],
+            blank: 0,
+        };
+        for (idx, tile) in BoardManager::tiles_of(board).iter().enumerate() {
+            match tile.get_value() as usize {
+                0 => occupancy.blank = line(idx),
+                value => occupancy.counts[line(idx) * n + goal_lines[value]] += 1,
+            }
+        }
+        Axis::of(n, &occupancy, goal_lines[0])
+    }
+
+    pub fn walking_distance(board: &Board) -> usize {
+        if BoardManager::size_of(board) as usize > MAX_SIZE {
+            return mahattan_distance(board, None);
+        }
+        Self::axis(board, true).distance as usize + Self::axis(board, false).distance as usize
+    }
+}
+
+impl HeuristicFn for WalkingDistance {
+    fn compute(&self, new_state: &Board, old_state: Option<&Board>) -> usize {
+        let n = BoardManager::size_of(new_state) as usize;
+        let goal = BoardManager::goal_of(new_state);
+        let Some(old_state) =
+            old_state.filter(|old_state| n <= MAX_SIZE && BoardManager::goal_of(old_state) == goal)
+        else {
+            return Self::walking_distance(new_state);
+        };
+
+        let tiles = BoardManager::tiles_of(old_state);
+        let mut parent = self.parent.borrow_mut();
+        let (rows, columns) = match &*parent {
+            Some(parent) if parent.tiles == tiles && parent.goal == goal => {
+                (parent.rows, parent.columns)
+            }
+            _ => {
+                let (rows, columns) = (Self::axis(old_state, true), Self::axis(old_state, false));
+                *parent = Some(Parent {
+                    tiles: tiles.to_vec(),
+                    goal: goal.to_vec(),
+                    rows,
+                    columns,
+                });
+                (rows, columns)
+            }
+        };
+        drop(parent);
+
+        let positions = self.goal.of(new_state);
+        let old_idx = BoardManager::empty_tile_idx(old_state) as usize;
+        let new_idx = BoardManager::empty_tile_idx(new_state) as usize;
+        let value = BoardManager::tiles_of(new_state)[old_idx].get_value() as usize;
+        let (goal_idx, goal_blank) = (positions[value], positions[0]);
+        let (rows, columns) = if old_idx % n == new_idx % n {
+            let rows = rows.slide(n, goal_idx / n, new_idx / n, old_idx / n, goal_blank / n);
+            (rows.unwrap_or_else(|| Self::axis(new_state, true)), columns)
+        } else {
+            let columns = columns.slide(n, goal_idx % n, new_idx % n, old_idx % n, goal_blank % n);
+            (
+                rows,
+                columns.unwrap_or_else(|| Self::axis(new_state, false)),
+            )
+        };
+        rows.distance as usize + columns.distance as usize
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        algorithms::informed_search::heuristic::{
+            mahattan_distance::{mahattan_distance, MahattanDistance},
+            perfect::PerfectHeuristic,
+            HeuristicFn,
+        },
+        board::{board::BoardManager, parser::BoardParser, scrambled},
+        AStarAlgorithms, Algorithms, GoalLayout, Scramble, SearchStats,
+    };
+
+    use super::WalkingDistance;
+
+    #[test]
+    fn it_should_lie_between_manhattan_and_the_optimal_length() {
+        let perfect = PerfectHeuristic::new();
+        for layout in [GoalLayout::RowMajor, GoalLayout::Snail] {
+            for seed in 0..20 {
+                let board = scrambled(3, layout, Scramble::Walk(60), seed);
+                let walking = WalkingDistance::walking_distance(&board);
+                assert!(walking >= mahattan_distance(&board, None));
+                assert!(walking <= perfect.distance(&board).unwrap());
+            }
+        }
+        let solved = BoardParser::parse("1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 0", true).unwrap();
+        assert_eq!(WalkingDistance::walking_distance(&solved), 0);
+        // tiles crossing each other in a line have to queue up through the empty cell
+        assert!((0..10).any(|seed| {
+            let board = scrambled(4, GoalLayout::RowMajor, Scramble::Walk(80), seed);
+            WalkingDistance::walking_distance(&board) > mahattan_distance(&board, None)
+        }));
+    }
+
+    #[test]
+    fn it_should_update_incrementally() {
+        // one instance for every goal, whose boards share their tiles between goals
+        let heuristic = WalkingDistance::new();
+        for (n, layout) in [(4, GoalLayout::BlankFirst), (3, GoalLayout::Snail)] {
+            for seed in 0..10 {
+                let mut board = scrambled(n, layout, Scramble::Walk(80), seed);
+                let other_goal = scrambled(n, layout, Scramble::Walk(80), seed + 10);
+                for step in 0..40 {
+                    let neighbors = BoardManager::neigbors_of(&board);
+                    for neighbor in &neighbors {
+                        assert_eq!(
+                            heuristic.compute(neighbor, Some(&board)),
+                            WalkingDistance::walking_distance(neighbor)
+                        );
+                        let target = BoardManager::tiles_of(&other_goal);
+                        let parent = BoardManager::retarget(&board, target);
+                        let neighbor = BoardManager::retarget(neighbor, target);
+                        assert_eq!(
+                            heuristic.compute(&neighbor, Some(&parent)),
+                            WalkingDistance::walking_distance(&neighbor)
+                        );
+                    }
+                    board = neighbors[step % neighbors.len()].clone();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_expand_fewer_boards_than_manhattan() {
+        let board = scrambled(4, GoalLayout::RowMajor, Scramble::Walk(26), 4);
+        let mut walking = SearchStats::new();
+        let mut manhattan = SearchStats::new();
+        AStarAlgorithms::with(Box::new(WalkingDistance::new()))
+            .solve_with(board.clone(), &mut walking)
+            .unwrap();
+        AStarAlgorithms::with(Box::new(MahattanDistance::new()))
+            .solve_with(board, &mut manhattan)
+            .unwrap();
+        assert_eq!(walking.solution_length, manhattan.solution_length);
+        assert!(walking.expanded < manhattan.expanded);
+    }
+}
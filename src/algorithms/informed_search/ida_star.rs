@@ -0,0 +1,427 @@
+use std::{
+    collections::LinkedList,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Sender},
+        Mutex,
+    },
+    thread,
+};
+
+use crate::{
+    algorithms::{observer::SearchObserver, pruning::MovePruning, Algorithms},
+    board::{
+        board::{Board, BoardManager},
+        moves::Move,
+    },
+};
+
+use super::{
+    heuristic::HeuristicFn,
+    parallel::{forward_events, Event, HeuristicFactory},
+};
+
+/// Number of subtrees handed out per worker in every iteration, so that a worker finishing
+/// early can pick up more work.
+const SUBTREES_PER_THREAD: usize = 8;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum IdaStarMode {
+    /// Stop every worker as soon as one of them reaches the goal.
+    #[default]
+    FirstSolution,
+    /// Finish the iteration that reached the goal and keep every optimal solution, or one of
+    /// every set of equivalent ones with [`ParallelIdaStarAlgorithms::pruning`].
+    AllOptimal,
+}
+
+/// Iterative deepening A* whose iterations run on several threads.
+///
+/// The top of the search tree is expanded breadth-first until there are enough subtrees for
+/// the workers, then every `f`-threshold iteration hands those subtrees out from a shared
+/// queue. The heuristic is created once per worker by a factory, like
+/// [`super::hda_star::HdaStarAlgorithms`]. Without [`ParallelIdaStarAlgorithms::pruning`] the
+/// only duplicates avoided are the moves undoing the last one.
+pub struct ParallelIdaStarAlgorithms {
+    threads: usize,
+    mode: IdaStarMode,
+    heuristic: HeuristicFactory,
+    pruning: Option<MovePruning>,
+}
+
+impl ParallelIdaStarAlgorithms {
+    pub fn with<F>(threads: usize, heuristic: F) -> Self
+    where
+        F: Fn() -> Box<dyn HeuristicFn> + Send + Sync + 'static,
+    {
+        Self {
+            threads: threads.max(1),
+            mode: IdaStarMode::default(),
+            heuristic: Box::new(heuristic),
+            pruning: None,
+        }
+    }
+
+    pub fn mode(mut self, mode: IdaStarMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Skips the moves `pruning` forbids along every path.
+    pub fn pruning(mut self, pruning: MovePruning) -> Self {
+        self.pruning = Some(pruning);
+        self
+    }
+
+    /// Solves `initial_state`, returning one optimal path in [`IdaStarMode::FirstSolution`]
+    /// and all of them, in a deterministic order, in [`IdaStarMode::AllOptimal`].
+    pub fn solve_all(
+        &mut self,
+        initial_state: Board,
+        observer: &mut dyn SearchObserver,
+    ) -> Vec<LinkedList<Board>> {
+        let n = BoardManager::size_of(&initial_state);
+        if !BoardManager::is_solvable(
+            n,
+            BoardManager::tiles_of(&initial_state),
+            BoardManager::goal_of(&initial_state),
+        ) {
+            return Vec::new();
+        }
+        if observer.is_cancelled() {
+            return Vec::new();
+        }
+        let heuristic = (self.heuristic)();
+        let frontier = self.split(initial_state, heuristic.as_ref(), observer);
+        let mut threshold = frontier
+            .iter()
+            .map(|(path, _)| path.len() - 1 + heuristic.compute(path.last().unwrap(), None))
+            .min()
+            .unwrap();
+
+        loop {
+            if observer.is_cancelled() {
+                return Vec::new();
+            }
+            let (mut solutions, next) = self.iterate(&frontier, threshold, observer);
+            if observer.is_cancelled() {
+                return Vec::new();
+            }
+            if !solutions.is_empty() {
+                solutions.sort_by_key(|(subtree, _)| *subtree);
+                if self.mode == IdaStarMode::FirstSolution {
+                    solutions.truncate(1);
+                }
+                let solutions: Vec<LinkedList<Board>> = solutions
+                    .into_iter()
+                    .map(|(_, path)| path.into_iter().collect())
+                    .collect();
+                solutions
+                    .iter()
+                    .for_each(|solution| observer.on_solution(solution));
+                return solutions;
+            }
+            match next {
+                Some(next) => threshold = next,
+                None => return Vec::new(),
+            }
+        }
+    }
+
+    /// Expands the tree breadth-first into paths to the roots of the subtrees the workers
+    /// share, along with the pruning state at their end. Goals are never expanded, so they stay
+    /// in the frontier as their own subtree.
+    fn split(
+        &self,
+        initial_state: Board,
+        heuristic: &dyn HeuristicFn,
+        observer: &mut dyn SearchObserver,
+    ) -> Vec<(Vec<Board>, usize)> {
+        let weight = heuristic.compute(&initial_state, None);
+        let mut frontier = vec![(
+            vec![BoardManager::assign_weight(initial_state, weight)],
+            MovePruning::START,
+        )];
+        while frontier.len() < self.threads * SUBTREES_PER_THREAD
+            && frontier
+                .iter()
+                .any(|(path, _)| !path.last().unwrap().match_goal())
+        {
+            frontier = frontier
+                .into_iter()
+                .flat_map(|(path, state)| {
+                    let node = path.last().unwrap();
+                    if node.match_goal() {
+                        return vec![(path, state)];
+                    }
+                    let children = children_of(&path, state, self.pruning.as_ref(), heuristic);
+                    observer.on_expand(node);
+                    children
+                        .iter()
+                        .for_each(|(child, _)| observer.on_generate(child, node));
+                    children
+                        .into_iter()
+                        .map(|(child, state)| {
+                            let mut path = path.clone();
+                            path.push(child);
+                            (path, state)
+                        })
+                        .collect()
+                })
+                .collect();
+        }
+        frontier
+    }
+
+    /// Runs one iteration over every subtree, returning the solutions found, tagged with the
+    /// index of their subtree, and the smallest `f` that exceeded `threshold`.
+    #[allow(clippy::type_complexity)]
+    fn iterate(
+        &self,
+        frontier: &[(Vec<Board>, usize)],
+        threshold: usize,
+        observer: &mut dyn SearchObserver,
+    ) -> (Vec<(usize, Vec<Board>)>, Option<usize>) {
+        let queue = Mutex::new(0..frontier.len());
+        let found = AtomicBool::new(false);
+        let stop = AtomicBool::new(false);
+        let (events, event_receiver) = mpsc::channel();
+        let factory = &self.heuristic;
+        let pruning = self.pruning.as_ref();
+        let (queue, found, stop, mode) = (&queue, &found, &stop, self.mode);
+
+        thread::scope(|scope| {
+            let handles: Vec<_> = (0..self.threads)
+                .map(|_| {
+                    let events = events.clone();
+                    scope.spawn(move || {
+                        let mut worker = Worker {
+                            heuristic: factory(),
+                            pruning,
+                            threshold,
+                            mode,
+                            found,
+                            stop,
+                            events,
+                            solutions: Vec::new(),
+                            next: None,
+                        };
+                        while let Some(subtree) = queue.lock().unwrap().next() {
+                            if worker.is_stopped() {
+                                break;
+                            }
+                            let (mut path, state) = frontier[subtree].clone();
+                            let solutions = worker.solutions.len();
+                            worker.search(&mut path, state);
+                            worker.solutions[solutions..]
+                                .iter_mut()
+                                .for_each(|solution| solution.0 = subtree);
+                        }
+                        (worker.solutions, worker.next)
+                    })
+                })
+                .collect();
+            drop(events);
+
+            forward_events(event_receiver, observer, stop);
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .fold(
+                    (Vec::new(), None),
+                    |(mut solutions, next): (Vec<(usize, Vec<Board>)>, Option<usize>),
+                     (found, found_next)| {
+                        solutions.extend(found);
+                        let next = match (next, found_next) {
+                            (Some(a), Some(b)) => Some(a.min(b)),
+                            (a, b) => a.or(b),
+                        };
+                        (solutions, next)
+                    },
+                )
+        })
+    }
+}
+
+impl Algorithms for ParallelIdaStarAlgorithms {
+    fn solve_with(
+        &mut self,
+        initial_state: Board,
+        observer: &mut dyn SearchObserver,
+    ) -> Option<LinkedList<Board>> {
+        self.solve_all(initial_state, observer).into_iter().next()
+    }
+}
+
+/// Neighbours of the last board of `path` that `pruning` allows from `state`, with the state
+/// they lead to. Without pruning only the move undoing the last one is skipped.
+fn children_of(
+    path: &[Board],
+    state: usize,
+    pruning: Option<&MovePruning>,
+    heuristic: &dyn HeuristicFn,
+) -> Vec<(Board, usize)> {
+    let node = path.last().unwrap();
+    let children: Vec<(Board, usize)> = match pruning {
+        Some(pruning) => Move::ALL
+            .iter()
+            .filter_map(|step| {
+                let state = pruning.next(state, *step)?;
+                Some((BoardManager::apply_move(node, *step)?, state))
+            })
+            .collect(),
+        None => {
+            let previous = path.len().checked_sub(2).map(|idx| &path[idx]);
+            BoardManager::neigbors_of(node)
+                .into_iter()
+                .filter(|child| Some(child) != previous)
+                .map(|child| (child, state))
+                .collect()
+        }
+    };
+    children
+        .into_iter()
+        .map(|(child, state)| {
+            let weight = heuristic.compute(&child, None);
+            (BoardManager::assign_weight(child, weight), state)
+        })
+        .collect()
+}
+
+struct Worker<'a> {
+    heuristic: Box<dyn HeuristicFn>,
+    pruning: Option<&'a MovePruning>,
+    threshold: usize,
+    mode: IdaStarMode,
+    found: &'a AtomicBool,
+    stop: &'a AtomicBool,
+    events: Sender<Event>,
+    solutions: Vec<(usize, Vec<Board>)>,
+    next: Option<usize>,
+}
+
+impl Worker<'_> {
+    fn is_stopped(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+            || (self.mode == IdaStarMode::FirstSolution && self.found.load(Ordering::Relaxed))
+    }
+
+    fn search(&mut self, path: &mut Vec<Board>, state: usize) {
+        let node = path.last().unwrap();
+        let f = path.len() - 1 + self.heuristic.compute(node, None);
+        if f > self.threshold {
+            self.next = Some(self.next.map_or(f, |next| next.min(f)));
+            return;
+        }
+        if node.match_goal() {
+            self.solutions.push((0, path.clone()));
+            self.found.store(true, Ordering::Relaxed);
+            return;
+        }
+        if self.is_stopped() {
+            return;
+        }
+        let children = children_of(path, state, self.pruning, self.heuristic.as_ref());
+        let boards = children.iter().map(|(child, _)| child.clone()).collect();
+        let _ = self.events.send(Event::Expand(node.clone(), boards));
+        for (child, state) in children {
+            path.push(child);
+            self.search(path, state);
+            path.pop();
+            if self.is_stopped() {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        algorithms::{
+            cancellation::CancelAfter,
+            informed_search::heuristic::mahattan_distance::MahattanDistance,
+        },
+        board::{board::BoardManager, scrambled},
+        Algorithms, Board, GoalLayout, MovePruning, NoopObserver, Scramble, SearchStats,
+    };
+
+    use super::{IdaStarMode, ParallelIdaStarAlgorithms};
+
+    #[test]
+    fn it_should_find_optimal_solutions() {
+        for threads in [1, 4] {
+            for seed in 0..3 {
+                let mut stats = SearchStats::new();
+                let path: Vec<Board> =
+                    ParallelIdaStarAlgorithms::with(threads, || Box::new(MahattanDistance::new()))
+                        .solve_with(
+                            scrambled(3, GoalLayout::RowMajor, Scramble::Distance(16), seed),
+                            &mut stats,
+                        )
+                        .unwrap()
+                        .into_iter()
+                        .collect();
+                assert_eq!(path.len(), 17);
+                assert_eq!(stats.solution_length, Some(16));
+                assert!(path.last().unwrap().match_goal());
+                assert!(path
+                    .windows(2)
+                    .all(|pair| BoardManager::move_of(&pair[0], &pair[1]).is_some()));
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_find_every_optimal_solution() {
+        let board = scrambled(3, GoalLayout::RowMajor, Scramble::Distance(12), 5);
+        let single = ParallelIdaStarAlgorithms::with(2, || Box::new(MahattanDistance::new()))
+            .solve_all(board.clone(), &mut NoopObserver);
+        assert_eq!(single.len(), 1);
+
+        let all = ParallelIdaStarAlgorithms::with(4, || Box::new(MahattanDistance::new()))
+            .mode(IdaStarMode::AllOptimal)
+            .solve_all(board.clone(), &mut NoopObserver);
+        assert_eq!(all.len(), 2);
+        assert!(all.iter().all(|path| path.len() == 13));
+        assert!(all.windows(2).all(|pair| pair[0] != pair[1]));
+        let sequential = ParallelIdaStarAlgorithms::with(1, || Box::new(MahattanDistance::new()))
+            .mode(IdaStarMode::AllOptimal)
+            .solve_all(board, &mut NoopObserver);
+        assert_eq!(all, sequential);
+    }
+
+    #[test]
+    fn it_should_generate_fewer_boards_with_move_pruning() {
+        for seed in 0..3 {
+            let board = scrambled(3, GoalLayout::RowMajor, Scramble::Distance(20), seed);
+            let mut plain = SearchStats::new();
+            let mut pruned = SearchStats::new();
+            // finishing the last iteration makes the counts independent of the move order
+            let all = ParallelIdaStarAlgorithms::with(2, || Box::new(MahattanDistance::new()))
+                .mode(IdaStarMode::AllOptimal)
+                .solve_all(board.clone(), &mut plain);
+            let some = ParallelIdaStarAlgorithms::with(2, || Box::new(MahattanDistance::new()))
+                .mode(IdaStarMode::AllOptimal)
+                .pruning(MovePruning::generate(8))
+                .solve_all(board, &mut pruned);
+            assert!(!some.is_empty() && some.len() <= all.len());
+            assert!(some
+                .iter()
+                .all(|path| path.len() == 21 && all.contains(path)));
+            assert!(pruned.generated < plain.generated);
+        }
+    }
+
+    #[test]
+    fn it_should_stop_when_cancelled_mid_search() {
+        let board = scrambled(4, GoalLayout::RowMajor, Scramble::Uniform, 1);
+        let mut observer = (SearchStats::new(), CancelAfter(100));
+        let path = ParallelIdaStarAlgorithms::with(2, || Box::new(MahattanDistance::new()))
+            .solve_with(board, &mut observer);
+        assert!(path.is_none());
+        assert!(observer.0.expanded >= 100);
+        assert!(observer.0.generated > 0);
+    }
+}
@@ -5,76 +5,157 @@ use crate::board::{
     cell::Tile,
 };
 
-use self::heuristic::HeuristicFn;
+use self::{heuristic::HeuristicFn, search_node::SearchNode};
 
-use super::Algorithms;
+use super::{
+    observer::{NoopObserver, SearchObserver},
+    stepper::{SearchStepper, Step},
+    Algorithms,
+};
 
+pub mod beam;
+pub mod bidirectional;
+pub mod hda_star;
 pub mod heuristic;
+pub mod ida_star;
+mod parallel;
+pub mod rbfs;
+pub mod search_node;
+pub mod sma_star;
 
 pub struct AStarAlgorithms {
     heuristic: Box<dyn HeuristicFn>,
+}
+
+impl AStarAlgorithms {
+    pub fn with(heuristic: Box<dyn HeuristicFn>) -> Self {
+        Self { heuristic }
+    }
+
+    pub fn stepper(&self, initial_state: Board) -> AStarStepper<'_> {
+        AStarStepper::new(self.heuristic.as_ref(), initial_state)
+    }
+}
+
+impl Algorithms for AStarAlgorithms {
+    fn solve_with(
+        &mut self,
+        initial_state: Board,
+        observer: &mut dyn SearchObserver,
+    ) -> Option<LinkedList<Board>> {
+        self.stepper(initial_state).run(observer)
+    }
+}
+
+pub struct AStarStepper<'a> {
+    heuristic: &'a dyn HeuristicFn,
+    pq: BinaryHeap<SearchNode>,
+    pushed: usize,
     caching: HashMap<Vec<Tile>, usize>,
     visited: HashSet<Vec<Tile>>,
     parents: HashMap<Vec<Tile>, Board>,
+    finished: bool,
 }
 
-impl AStarAlgorithms {
-    pub fn with(heuristic: Box<dyn HeuristicFn>) -> Self {
-        Self {
+impl<'a> AStarStepper<'a> {
+    pub fn new(heuristic: &'a dyn HeuristicFn, initial_state: Board) -> Self {
+        let mut stepper = Self {
             heuristic,
+            pq: BinaryHeap::new(),
+            pushed: 0,
             caching: HashMap::new(),
             visited: HashSet::new(),
             parents: HashMap::new(),
-        }
+            finished: false,
+        };
+        // initialize weight for initial_state
+        let weight = heuristic.compute(&initial_state, None);
+        stepper.push(BoardManager::assign_weight(initial_state, weight));
+        stepper
+    }
+
+    fn push(&mut self, board: Board) {
+        let h = BoardManager::heuristic_value_of(&board);
+        let g = BoardManager::total_weight(&board) - h;
+        self.pq.push(SearchNode::new(board, g, h, self.pushed));
+        self.pushed += 1;
     }
 }
 
-impl Algorithms for AStarAlgorithms {
-    fn solve(&mut self, initial_state: Board) -> Option<std::collections::LinkedList<Board>> {
-        let mut solutions = LinkedList::new();
-        let mut pq = BinaryHeap::new();
-        // initialize weight for initial_state
-        let weight = self.heuristic.compute(&initial_state, None);
-        let initial_state = BoardManager::assign_weight(initial_state, weight);
-        pq.push(initial_state);
-        while let Some(current) = pq.pop() {
-            let tiles = BoardManager::tiles_of(&current).to_vec();
-            if self.visited.contains(&tiles) {
-                continue;
+impl SearchStepper for AStarStepper<'_> {
+    fn step(&mut self, observer: &mut dyn SearchObserver) -> Step {
+        let Some(current) = self.pq.pop().map(SearchNode::into_board) else {
+            self.finished = true;
+            return Step::Exhausted;
+        };
+        let tiles = BoardManager::tiles_of(&current).to_vec();
+        if self.visited.contains(&tiles) {
+            observer.on_duplicate(&current);
+            return Step::Skipped(current);
+        }
+        if current.match_goal() {
+            let mut solutions = LinkedList::new();
+            solutions.push_front(current.clone());
+            let mut tiles = tiles;
+            while let Some(parent) = self.parents.get(&tiles) {
+                solutions.push_front(parent.clone());
+                tiles = BoardManager::tiles_of(parent).to_vec();
             }
-            if current.match_goal() {
-                solutions.push_front(current.clone());
-                let mut tiles = tiles;
-                while let Some(parent) = self.parents.get(&tiles) {
-                    solutions.push_front(parent.clone());
-                    tiles = BoardManager::tiles_of(parent).to_vec();
+            observer.on_solution(&solutions);
+            self.finished = true;
+            return Step::Solved(solutions);
+        }
+        observer.on_expand(&current);
+        self.visited.insert(tiles);
+        let (neighbors, duplicates): (Vec<Board>, Vec<Board>) = BoardManager::neigbors_of(&current)
+            .into_iter()
+            .partition(|board| !self.visited.contains(BoardManager::tiles_of(board)));
+        duplicates
+            .iter()
+            .for_each(|board| observer.on_duplicate(board));
+        for board in neighbors {
+            let weight = self.heuristic.compute(&board, Some(&current));
+            let board = BoardManager::assign_weight(board, weight);
+            let weight = BoardManager::total_weight(&board);
+            let tiles = BoardManager::tiles_of(&board).to_vec();
+            if let Some(value) = self.caching.get_mut(&tiles) {
+                if *value > weight {
+                    *value = weight;
+                    observer.on_generate(&board, &current);
+                    self.parents.insert(tiles, current.clone());
+                    self.push(board);
+                } else {
+                    observer.on_duplicate(&board);
                 }
-                return Some(solutions);
+            } else {
+                self.caching.insert(tiles.clone(), weight);
+                observer.on_generate(&board, &current);
+                self.parents.insert(tiles, current.clone());
+                self.push(board);
             }
-            self.visited.insert(tiles);
-            BoardManager::neigbors_of(&current)
-                .into_iter()
-                .filter(|board| !self.visited.contains(BoardManager::tiles_of(board)))
-                .map(|board| {
-                    let weight = self.heuristic.compute(&board, Some(&current));
-                    BoardManager::assign_weight(board, weight)
-                })
-                .for_each(|board| {
-                    let weight = BoardManager::total_weight(&board);
-                    let tiles = BoardManager::tiles_of(&board).to_vec();
-                    if let Some(value) = self.caching.get_mut(&tiles) {
-                        if *value > weight {
-                            *value = weight;
-                            self.parents.insert(tiles, current.clone());
-                            pq.push(board);
-                        }
-                    } else {
-                        self.caching.insert(tiles.clone(), weight);
-                        self.parents.insert(tiles, current.clone());
-                        pq.push(board);
-                    }
-                });
         }
-        None
+        Step::Expanded(current)
+    }
+
+    fn frontier(&self) -> Vec<&Board> {
+        let mut frontier: Vec<&SearchNode> = self.pq.iter().collect();
+        frontier.sort_by(|a, b| b.cmp(a));
+        frontier.into_iter().map(SearchNode::board).collect()
+    }
+
+    fn closed(&self) -> Vec<&[Tile]> {
+        self.visited.iter().map(Vec::as_slice).collect()
+    }
+
+    fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
+
+impl Iterator for AStarStepper<'_> {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        (!self.finished).then(|| self.step(&mut NoopObserver))
     }
 }
@@ -0,0 +1,51 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{Receiver, RecvTimeoutError},
+    },
+    time::Duration,
+};
+
+use crate::{algorithms::observer::SearchObserver, board::board::Board};
+
+use super::heuristic::HeuristicFn;
+
+/// How long threads wait on a channel before checking whether they should stop.
+pub(super) const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Creates the heuristic of every worker, since heuristics may keep per-search caches.
+pub(super) type HeuristicFactory = Box<dyn Fn() -> Box<dyn HeuristicFn> + Send + Sync>;
+
+/// What a worker did, to be reported to the observer.
+pub(super) enum Event {
+    Expand(Board, Vec<Board>),
+    Duplicate(Board),
+}
+
+/// Reports the events of the workers to `observer` until every worker has dropped its sender,
+/// raising `stop` once the observer asks for the search to be cancelled.
+///
+/// The observer is not shareable, so workers report to the thread that started the search,
+/// which runs this loop.
+pub(super) fn forward_events(
+    events: Receiver<Event>,
+    observer: &mut dyn SearchObserver,
+    stop: &AtomicBool,
+) {
+    loop {
+        match events.recv_timeout(POLL_INTERVAL) {
+            Ok(Event::Expand(board, children)) => {
+                observer.on_expand(&board);
+                children
+                    .iter()
+                    .for_each(|child| observer.on_generate(child, &board));
+            }
+            Ok(Event::Duplicate(board)) => observer.on_duplicate(&board),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+        if observer.is_cancelled() {
+            stop.store(true, Ordering::Relaxed);
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::LinkedList;
+
+use crate::{
+    algorithms::{observer::SearchObserver, Algorithms},
+    board::board::{Board, BoardManager},
+};
+
+use super::heuristic::HeuristicFn;
+
+/// Recursive best-first search: explores like A* while only keeping the current path and the
+/// siblings along it, so memory stays linear in the solution depth.
+///
+/// When a subtree is abandoned its best `f` is backed up to its root, which lets the search
+/// resume it later without re-expanding it from scratch as IDA* would. The solution is optimal
+/// with an admissible heuristic.
+pub struct RbfsAlgorithms {
+    heuristic: Box<dyn HeuristicFn>,
+}
+
+impl RbfsAlgorithms {
+    pub fn with(heuristic: Box<dyn HeuristicFn>) -> Self {
+        Self { heuristic }
+    }
+
+    fn search(
+        &self,
+        node: &Board,
+        parent: Option<&Board>,
+        g: usize,
+        f: usize,
+        bound: usize,
+        observer: &mut dyn SearchObserver,
+    ) -> Result<LinkedList<Board>, usize> {
+        if node.match_goal() {
+            return Ok(LinkedList::from([node.clone()]));
+        }
+        if observer.is_cancelled() {
+            return Err(usize::MAX);
+        }
+        observer.on_expand(node);
+        let mut successors: Vec<(Board, usize)> = BoardManager::neigbors_of(node)
+            .into_iter()
+            .filter(|child| Some(child) != parent)
+            .map(|child| {
+                observer.on_generate(&child, node);
+                let weight = self.heuristic.compute(&child, Some(node));
+                // a child is never cheaper than the value backed up to its parent
+                (
+                    BoardManager::assign_weight(child, weight),
+                    (g + 1 + weight).max(f),
+                )
+            })
+            .collect();
+        if successors.is_empty() {
+            return Err(usize::MAX);
+        }
+        loop {
+            successors.sort_by_key(|(_, f)| *f);
+            let best = successors[0].1;
+            if best > bound || best == usize::MAX {
+                return Err(best);
+            }
+            let alternative = successors.get(1).map_or(usize::MAX, |(_, f)| *f);
+            match self.search(
+                &successors[0].0,
+                Some(node),
+                g + 1,
+                best,
+                bound.min(alternative),
+                observer,
+            ) {
+                Ok(mut path) => {
+                    path.push_front(node.clone());
+                    return Ok(path);
+                }
+                Err(backed_up) => successors[0].1 = backed_up,
+            }
+            if observer.is_cancelled() {
+                return Err(usize::MAX);
+            }
+        }
+    }
+}
+
+impl Algorithms for RbfsAlgorithms {
+    fn solve_with(
+        &mut self,
+        initial_state: Board,
+        observer: &mut dyn SearchObserver,
+    ) -> Option<LinkedList<Board>> {
+        let weight = self.heuristic.compute(&initial_state, None);
+        let initial_state = BoardManager::assign_weight(initial_state, weight);
+        let path = self
+            .search(&initial_state, None, 0, weight, usize::MAX, observer)
+            .ok()?;
+        observer.on_solution(&path);
+        Some(path)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        algorithms::{
+            cancellation::CancelAfter,
+            informed_search::heuristic::mahattan_distance::MahattanDistance,
+        },
+        board::{board::BoardManager, scrambled},
+        Algorithms, Board, GoalLayout, Scramble, SearchStats,
+    };
+
+    use super::RbfsAlgorithms;
+
+    #[test]
+    fn it_should_find_optimal_solutions() {
+        for seed in 0..4 {
+            let mut stats = SearchStats::new();
+            let path: Vec<Board> = RbfsAlgorithms::with(Box::new(MahattanDistance::new()))
+                .solve_with(
+                    scrambled(3, GoalLayout::RowMajor, Scramble::Distance(18), seed),
+                    &mut stats,
+                )
+                .unwrap()
+                .into_iter()
+                .collect();
+            assert_eq!(path.len(), 19);
+            assert_eq!(stats.solution_length, Some(18));
+            assert!(path.last().unwrap().match_goal());
+            assert!(path
+                .windows(2)
+                .all(|pair| BoardManager::move_of(&pair[0], &pair[1]).is_some()));
+        }
+    }
+
+    #[test]
+    fn it_should_solve_a_solved_board_without_expanding() {
+        let board = scrambled(3, GoalLayout::RowMajor, Scramble::Distance(0), 1);
+        let mut stats = SearchStats::new();
+        let path = RbfsAlgorithms::with(Box::new(MahattanDistance::new()))
+            .solve_with(board, &mut stats)
+            .unwrap();
+        assert_eq!(path.len(), 1);
+        assert_eq!(stats.expanded, 0);
+    }
+
+    #[test]
+    fn it_should_stop_when_cancelled_mid_search() {
+        let board = scrambled(4, GoalLayout::RowMajor, Scramble::Uniform, 1);
+        let mut observer = (SearchStats::new(), CancelAfter(100));
+        let path = RbfsAlgorithms::with(Box::new(MahattanDistance::new()))
+            .solve_with(board, &mut observer);
+        assert!(path.is_none());
+        assert_eq!(observer.0.expanded, 100);
+        assert!(observer.0.generated > 0);
+    }
+}
@@ -0,0 +1,109 @@
+use std::cmp::Ordering;
+
+use crate::board::board::Board;
+
+/// A board waiting in an open list, ordered by search priority instead of by its tiles.
+///
+/// A [`std::collections::BinaryHeap`] of nodes pops the lowest `f = g + h` first; among equal
+/// `f` the node with the higher `g`, then the lower `h`, and last the one pushed most recently,
+/// as told by its `sequence` number. Preferring deep nodes heads straight for the goal once the
+/// optimal cost is reached, and the sequence number makes the order total, so nodes are only
+/// equal to themselves.
+#[derive(Debug, Clone)]
+pub struct SearchNode {
+    board: Board,
+    g: usize,
+    h: usize,
+    sequence: usize,
+}
+
+impl SearchNode {
+    pub fn new(board: Board, g: usize, h: usize, sequence: usize) -> Self {
+        Self {
+            board,
+            g,
+            h,
+            sequence,
+        }
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn into_board(self) -> Board {
+        self.board
+    }
+
+    pub fn f(&self) -> usize {
+        self.g + self.h
+    }
+
+    pub fn g(&self) -> usize {
+        self.g
+    }
+
+    pub fn h(&self) -> usize {
+        self.h
+    }
+}
+
+impl PartialEq for SearchNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for SearchNode {}
+
+impl PartialOrd for SearchNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SearchNode {
+    // greater nodes are popped first
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .f()
+            .cmp(&self.f())
+            .then(self.g.cmp(&other.g))
+            .then(other.h.cmp(&self.h))
+            .then(self.sequence.cmp(&other.sequence))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BinaryHeap;
+
+    use crate::board::parser::BoardParser;
+
+    use super::SearchNode;
+
+    #[test]
+    fn it_should_pop_nodes_by_priority() {
+        let board = BoardParser::parse("1 2 3 4 5 6 7 8 0", true).unwrap();
+        let mut heap = BinaryHeap::from([
+            SearchNode::new(board.clone(), 3, 4, 0),
+            SearchNode::new(board.clone(), 1, 5, 1),
+            SearchNode::new(board.clone(), 2, 4, 2),
+            SearchNode::new(board.clone(), 5, 1, 3),
+            SearchNode::new(board.clone(), 5, 1, 4),
+        ]);
+        let order: Vec<(usize, usize)> = std::iter::from_fn(|| heap.pop())
+            .map(|node| (node.f(), node.sequence))
+            .collect();
+        // lowest f, then deepest, then last pushed
+        assert_eq!(order, vec![(6, 4), (6, 3), (6, 2), (6, 1), (7, 0)]);
+    }
+
+    #[test]
+    fn it_should_only_equal_itself() {
+        let board = BoardParser::parse("1 2 3 4 5 6 7 8 0", true).unwrap();
+        let node = SearchNode::new(board.clone(), 2, 2, 0);
+        assert_eq!(node, node.clone());
+        assert_ne!(node, SearchNode::new(board, 2, 2, 1));
+    }
+}
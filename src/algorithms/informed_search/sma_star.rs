@@ -0,0 +1,443 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeSet, LinkedList},
+};
+
+use crate::{
+    algorithms::{observer::SearchObserver, Algorithms},
+    board::{
+        board::{Board, BoardManager},
+        cell::Tile,
+    },
+};
+
+use super::heuristic::HeuristicFn;
+
+struct Node {
+    board: Board,
+    g: usize,
+    /// Backed-up cost: the smallest `f` known below this node.
+    f: usize,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    /// Successors dropped to free memory, with the `f` they had when they were dropped.
+    forgotten: Vec<(Vec<Tile>, usize)>,
+    expanded: bool,
+}
+
+impl Node {
+    fn forgotten_f(&self) -> Option<usize> {
+        self.forgotten.iter().map(|(_, f)| *f).min()
+    }
+
+    /// Priority of the node in the open list: its own `f` until it is expanded, then the best
+    /// successor it has to regenerate.
+    fn open_key(&self) -> Option<usize> {
+        if self.expanded {
+            self.forgotten_f()
+        } else {
+            Some(self.f)
+        }
+    }
+}
+
+/// Simplified memory-bounded A*: searches like A* but never keeps more than `max_nodes` nodes.
+///
+/// When memory is full the worst leaf, the one with the highest `f` and the shallowest among
+/// those, is dropped and its `f` is remembered by its parent, which regenerates it once it is
+/// the most promising node again. With an admissible heuristic the solution is optimal as long
+/// as `max_nodes` can hold the optimal path; otherwise no solution is returned.
+pub struct SmaStarAlgorithms {
+    heuristic: Box<dyn HeuristicFn>,
+    max_nodes: usize,
+    peak_nodes: usize,
+}
+
+impl SmaStarAlgorithms {
+    pub fn with(heuristic: Box<dyn HeuristicFn>, max_nodes: usize) -> Self {
+        Self {
+            heuristic,
+            max_nodes: max_nodes.max(1),
+            peak_nodes: 0,
+        }
+    }
+
+    /// Largest number of nodes held at once by the last solve.
+    pub fn peak_nodes(&self) -> usize {
+        self.peak_nodes
+    }
+}
+
+impl Algorithms for SmaStarAlgorithms {
+    fn solve_with(
+        &mut self,
+        initial_state: Board,
+        observer: &mut dyn SearchObserver,
+    ) -> Option<LinkedList<Board>> {
+        let weight = self.heuristic.compute(&initial_state, None);
+        let mut memory = Memory::new(Node {
+            board: BoardManager::assign_weight(initial_state, weight),
+            g: 0,
+            f: weight,
+            parent: None,
+            children: Vec::new(),
+            forgotten: Vec::new(),
+            expanded: false,
+        });
+        let result = memory.search(self.heuristic.as_ref(), self.max_nodes, observer);
+        self.peak_nodes = memory.peak;
+        let path = result?;
+        observer.on_solution(&path);
+        Some(path)
+    }
+}
+
+/// Orders nodes by `f`, deepest first among equal `f`.
+type Key = (usize, Reverse<usize>, usize);
+
+struct Memory {
+    nodes: Vec<Option<Node>>,
+    free: Vec<usize>,
+    len: usize,
+    peak: usize,
+    /// Nodes that still have successors to generate, best first.
+    open: BTreeSet<Key>,
+    /// Nodes without successors in memory, worst last.
+    leaves: BTreeSet<Key>,
+}
+
+impl Memory {
+    fn new(root: Node) -> Self {
+        let mut memory = Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+            peak: 0,
+            open: BTreeSet::new(),
+            leaves: BTreeSet::new(),
+        };
+        memory.insert(root);
+        memory
+    }
+
+    fn node(&self, id: usize) -> &Node {
+        self.nodes[id].as_ref().unwrap()
+    }
+
+    fn node_mut(&mut self, id: usize) -> &mut Node {
+        self.nodes[id].as_mut().unwrap()
+    }
+
+    fn insert(&mut self, node: Node) -> usize {
+        let id = match self.free.pop() {
+            Some(id) => id,
+            None => {
+                self.nodes.push(None);
+                self.nodes.len() - 1
+            }
+        };
+        self.open.insert((node.f, Reverse(node.g), id));
+        self.leaves.insert((node.f, Reverse(node.g), id));
+        self.nodes[id] = Some(node);
+        self.len += 1;
+        self.peak = self.peak.max(self.len);
+        id
+    }
+
+    fn keys_of(&self, id: usize) -> (Option<Key>, Option<Key>) {
+        let node = self.node(id);
+        let depth = Reverse(node.g);
+        (
+            node.open_key().map(|key| (key, depth, id)),
+            node.children.is_empty().then_some((node.f, depth, id)),
+        )
+    }
+
+    /// Removes `id` from the open and leaf lists, runs `update` and puts it back where it
+    /// belongs, so the lists never hold stale keys.
+    fn update(&mut self, id: usize, update: impl FnOnce(&mut Node)) {
+        let (open_key, leaf_key) = self.keys_of(id);
+        if let Some(key) = open_key {
+            self.open.remove(&key);
+        }
+        if let Some(key) = leaf_key {
+            self.leaves.remove(&key);
+        }
+        update(self.node_mut(id));
+        let (open_key, leaf_key) = self.keys_of(id);
+        if let Some(key) = open_key {
+            self.open.insert(key);
+        }
+        if let Some(key) = leaf_key {
+            self.leaves.insert(key);
+        }
+    }
+
+    /// Drops the worst leaf other than `keep` and its children, remembering its `f` in its
+    /// parent. The children of `keep` stay, since they are left out of the successors it is
+    /// about to generate.
+    fn forget_worst_leaf(&mut self, keep: usize) -> bool {
+        let Some(&(f, depth, id)) = self.leaves.iter().rev().find(|(_, _, id)| {
+            *id != keep && matches!(self.node(*id).parent, Some(parent) if parent != keep)
+        }) else {
+            return false;
+        };
+        self.open.remove(&(f, depth, id));
+        self.leaves.remove(&(f, depth, id));
+        let node = self.nodes[id].take().unwrap();
+        self.free.push(id);
+        self.len -= 1;
+        let tiles = BoardManager::tiles_of(&node.board).to_vec();
+        self.update(node.parent.unwrap(), |parent| {
+            parent.children.retain(|child| *child != id);
+            parent.forgotten.push((tiles, node.f));
+        });
+        true
+    }
+
+    /// Recomputes the backed-up `f` of `id` and its ancestors from their successors.
+    fn back_up(&mut self, mut id: usize) {
+        loop {
+            let node = self.node(id);
+            let f = node
+                .children
+                .iter()
+                .map(|child| self.node(*child).f)
+                .chain(node.forgotten_f())
+                .min()
+                .unwrap_or(usize::MAX);
+            if f == node.f {
+                return;
+            }
+            let parent = node.parent;
+            self.update(id, |node| node.f = f);
+            match parent {
+                Some(parent) => id = parent,
+                None => return,
+            }
+        }
+    }
+
+    fn path_to(&self, mut id: usize) -> LinkedList<Board> {
+        let mut path = LinkedList::new();
+        loop {
+            let node = self.node(id);
+            path.push_front(node.board.clone());
+            match node.parent {
+                Some(parent) => id = parent,
+                None => return path,
+            }
+        }
+    }
+
+    fn search(
+        &mut self,
+        heuristic: &dyn HeuristicFn,
+        max_nodes: usize,
+        observer: &mut dyn SearchObserver,
+    ) -> Option<LinkedList<Board>> {
+        loop {
+            if observer.is_cancelled() {
+                return None;
+            }
+            let &(key, _, id) = self.open.first()?;
+            if key == usize::MAX {
+                // every remaining path needs more memory than there is
+                return None;
+            }
+            let node = self.node(id);
+            if node.board.match_goal() {
+                return Some(self.path_to(id));
+            }
+
+            // generate the successors that are not in memory, skipping the way back
+            let grandparent = node.parent.map(|parent| &self.node(parent).board);
+            let present: Vec<&[Tile]> = node
+                .children
+                .iter()
+                .map(|child| BoardManager::tiles_of(&self.node(*child).board))
+                .collect();
+            let successors: Vec<(Board, Option<usize>)> = BoardManager::neigbors_of(&node.board)
+                .into_iter()
+                .filter(|child| Some(child) != grandparent)
+                .filter(|child| !present.contains(&BoardManager::tiles_of(child)))
+                .map(|child| {
+                    let remembered = node
+                        .forgotten
+                        .iter()
+                        .find(|(tiles, _)| tiles.as_slice() == BoardManager::tiles_of(&child))
+                        .map(|(_, f)| *f);
+                    (child, remembered)
+                })
+                .collect();
+
+            while self.len + successors.len() > max_nodes {
+                if !self.forget_worst_leaf(id) {
+                    break;
+                }
+            }
+            let node = self.node(id);
+            let (g, f, board) = (node.g, node.f, node.board.clone());
+            observer.on_expand(&board);
+            if self.len + successors.len() > max_nodes {
+                // not even this node's successors fit, so it can never be expanded
+                self.update(id, |node| {
+                    node.expanded = true;
+                    node.forgotten.clear();
+                    node.f = usize::MAX;
+                });
+                if let Some(parent) = self.node(id).parent {
+                    self.back_up(parent);
+                }
+                continue;
+            }
+            let regenerated: Vec<Vec<Tile>> = successors
+                .iter()
+                .map(|(child, _)| BoardManager::tiles_of(child).to_vec())
+                .collect();
+            let children: Vec<usize> = successors
+                .into_iter()
+                .map(|(child, remembered)| {
+                    observer.on_generate(&child, &board);
+                    let weight = heuristic.compute(&child, Some(&board));
+                    let child_f = (g + 1 + weight).max(f).max(remembered.unwrap_or(0));
+                    self.insert(Node {
+                        board: BoardManager::assign_weight(child, weight),
+                        g: g + 1,
+                        f: child_f,
+                        parent: Some(id),
+                        children: Vec::new(),
+                        forgotten: Vec::new(),
+                        expanded: false,
+                    })
+                })
+                .collect();
+            self.update(id, |node| {
+                node.expanded = true;
+                node.forgotten
+                    .retain(|(tiles, _)| !regenerated.contains(tiles));
+                node.children.extend(children);
+            });
+            self.back_up(id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        algorithms::{
+            cancellation::CancelAfter,
+            informed_search::heuristic::{
+                mahattan_distance::MahattanDistance, misplaced_tiles::MisplacedTiles, HeuristicFn,
+            },
+        },
+        board::{board::BoardManager, scrambled},
+        AStarAlgorithms, Algorithms, Board, GoalLayout, Scramble, SearchObserver, SearchStats,
+    };
+
+    use super::{Memory, Node, SmaStarAlgorithms};
+
+    #[test]
+    fn it_should_find_optimal_solutions_within_memory() {
+        for (max_nodes, seed) in [(100_000, 0), (200, 1), (60, 2)] {
+            let mut solver = SmaStarAlgorithms::with(Box::new(MahattanDistance::new()), max_nodes);
+            let mut stats = SearchStats::new();
+            let path: Vec<Board> = solver
+                .solve_with(
+                    scrambled(3, GoalLayout::RowMajor, Scramble::Distance(16), seed),
+                    &mut stats,
+                )
+                .unwrap()
+                .into_iter()
+                .collect();
+            assert_eq!(path.len(), 17);
+            assert_eq!(stats.solution_length, Some(16));
+            assert!(solver.peak_nodes() <= max_nodes);
+            assert!(path
+                .windows(2)
+                .all(|pair| BoardManager::move_of(&pair[0], &pair[1]).is_some()));
+        }
+    }
+
+    /// Stops the search after a number of expansions.
+    struct Budget(usize);
+
+    impl SearchObserver for Budget {
+        fn on_expand(&mut self, _board: &Board) {
+            self.0 = self.0.saturating_sub(1);
+        }
+
+        fn is_cancelled(&self) -> bool {
+            self.0 == 0
+        }
+    }
+
+    #[test]
+    fn it_should_remember_every_successor_it_drops() {
+        let board = scrambled(3, GoalLayout::RowMajor, Scramble::Distance(18), 3);
+        for expansions in 1..300 {
+            let weight = MahattanDistance::new().compute(&board, None);
+            let mut memory = Memory::new(Node {
+                board: BoardManager::assign_weight(board.clone(), weight),
+                g: 0,
+                f: weight,
+                parent: None,
+                children: Vec::new(),
+                forgotten: Vec::new(),
+                expanded: false,
+            });
+            memory.search(&MahattanDistance::new(), 24, &mut Budget(expansions));
+
+            // an expanded node holds or remembers every successor but the way back
+            for node in memory.nodes.iter().flatten().filter(|node| node.expanded) {
+                let parent = node.parent.map(|parent| &memory.node(parent).board);
+                for successor in BoardManager::neigbors_of(&node.board) {
+                    let tiles = BoardManager::tiles_of(&successor);
+                    assert!(
+                        Some(&successor) == parent
+                            || node.forgotten.iter().any(|(other, _)| other == tiles)
+                            || node.children.iter().any(|child| {
+                                BoardManager::tiles_of(&memory.node(*child).board) == tiles
+                            })
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_match_a_star_with_little_memory() {
+        for seed in 0..10 {
+            let board = scrambled(3, GoalLayout::RowMajor, Scramble::Distance(16), seed);
+            let expected = AStarAlgorithms::with(Box::new(MisplacedTiles))
+                .solve(board.clone())
+                .unwrap();
+            for max_nodes in [19, 20, 24, 32] {
+                let path = SmaStarAlgorithms::with(Box::new(MisplacedTiles), max_nodes)
+                    .solve(board.clone())
+                    .unwrap();
+                assert_eq!(path.len(), expected.len());
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_give_up_when_the_path_does_not_fit() {
+        let mut solver = SmaStarAlgorithms::with(Box::new(MahattanDistance::new()), 8);
+        let board = scrambled(3, GoalLayout::RowMajor, Scramble::Distance(12), 1);
+        assert!(solver.solve(board).is_none());
+        assert!(solver.peak_nodes() <= 8);
+    }
+
+    #[test]
+    fn it_should_stop_when_cancelled_mid_search() {
+        let board = scrambled(4, GoalLayout::RowMajor, Scramble::Uniform, 1);
+        let mut observer = (SearchStats::new(), CancelAfter(100));
+        let path = SmaStarAlgorithms::with(Box::new(MahattanDistance::new()), 1000)
+            .solve_with(board, &mut observer);
+        assert!(path.is_none());
+        assert_eq!(observer.0.expanded, 100);
+        assert!(observer.0.generated > 0);
+    }
+}
@@ -2,9 +2,49 @@ use std::collections::LinkedList;
 
 use crate::Board;
 
+use self::{
+    cancellation::{CancellationToken, CancellationWatch, SearchResult, SolveOutcome},
+    observer::{NoopObserver, SearchObserver, SearchStats},
+};
+
+pub mod cancellation;
+pub mod dot;
 pub mod informed_search;
+pub mod observer;
+pub mod pruning;
+pub mod reduction;
+pub mod report;
+pub mod state_space;
+pub mod stepper;
 pub mod uninformed_search;
 
 pub trait Algorithms {
-    fn solve(&mut self, initial_state: Board) -> Option<LinkedList<Board>>;
+    fn solve(&mut self, initial_state: Board) -> Option<LinkedList<Board>> {
+        self.solve_with(initial_state, &mut NoopObserver)
+    }
+
+    /// Same as [`Algorithms::solve`], reporting the progress of the search to `observer`.
+    fn solve_with(
+        &mut self,
+        initial_state: Board,
+        observer: &mut dyn SearchObserver,
+    ) -> Option<LinkedList<Board>>;
+
+    /// Solves until `token` is cancelled, returning the statistics gathered either way.
+    fn solve_until_cancelled(
+        &mut self,
+        initial_state: Board,
+        token: &CancellationToken,
+    ) -> SearchResult {
+        let mut observer = (SearchStats::new(), CancellationWatch::new(token));
+        let outcome = match self.solve_with(initial_state, &mut observer) {
+            Some(path) => SolveOutcome::Solved(path),
+            None if observer.1.stopped() => SolveOutcome::Cancelled,
+            None => SolveOutcome::Exhausted,
+        };
+        SearchResult {
+            outcome,
+            stats: observer.0,
+        }
+    }
 }
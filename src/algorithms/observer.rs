@@ -0,0 +1,139 @@
+use std::collections::LinkedList;
+
+use crate::Board;
+
+/// Hooks every [`crate::Algorithms`] implementation calls while it searches. All methods do
+/// nothing by default, so observers only implement the events they care about.
+pub trait SearchObserver {
+    /// `board` is about to have its neighbours generated.
+    fn on_expand(&mut self, _board: &Board) {}
+
+    /// `board` was generated from `parent` and will be considered by the search.
+    fn on_generate(&mut self, _board: &Board, _parent: &Board) {}
+
+    /// `board` was dropped because the search has already seen it.
+    fn on_duplicate(&mut self, _board: &Board) {}
+
+    /// The search found `path`, from the initial state to the goal.
+    fn on_solution(&mut self, _path: &LinkedList<Board>) {}
+
+    /// Polled by the solvers between expansions; once it returns `true` they stop and report
+    /// no solution.
+    fn is_cancelled(&self) -> bool {
+        false
+    }
+}
+
+pub struct NoopObserver;
+
+impl SearchObserver for NoopObserver {}
+
+/// Counts the events raised during a search.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SearchStats {
+    pub expanded: usize,
+    pub generated: usize,
+    pub duplicates: usize,
+    /// Number of moves of the solution, once one is found.
+    pub solution_length: Option<usize>,
+}
+
+impl SearchStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SearchObserver for SearchStats {
+    fn on_expand(&mut self, _board: &Board) {
+        self.expanded += 1;
+    }
+
+    fn on_generate(&mut self, _board: &Board, _parent: &Board) {
+        self.generated += 1;
+    }
+
+    fn on_duplicate(&mut self, _board: &Board) {
+        self.duplicates += 1;
+    }
+
+    fn on_solution(&mut self, path: &LinkedList<Board>) {
+        self.solution_length = Some(path.len().saturating_sub(1));
+    }
+}
+
+/// Forwards every event to both observers, e.g. to count statistics while recording a trace.
+impl<A: SearchObserver, B: SearchObserver> SearchObserver for (A, B) {
+    fn on_expand(&mut self, board: &Board) {
+        self.0.on_expand(board);
+        self.1.on_expand(board);
+    }
+
+    fn on_generate(&mut self, board: &Board, parent: &Board) {
+        self.0.on_generate(board, parent);
+        self.1.on_generate(board, parent);
+    }
+
+    fn on_duplicate(&mut self, board: &Board) {
+        self.0.on_duplicate(board);
+        self.1.on_duplicate(board);
+    }
+
+    fn on_solution(&mut self, path: &LinkedList<Board>) {
+        self.0.on_solution(path);
+        self.1.on_solution(path);
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.0.is_cancelled() || self.1.is_cancelled()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::LinkedList;
+
+    use crate::{
+        algorithms::informed_search::heuristic::mahattan_distance::MahattanDistance,
+        board::parser::BoardParser, AStarAlgorithms, Algorithms, Board, DfsAlgorithms,
+        UcsAlgorithms,
+    };
+
+    use super::{SearchObserver, SearchStats};
+
+    #[derive(Default)]
+    struct Trace {
+        events: Vec<&'static str>,
+    }
+
+    impl SearchObserver for Trace {
+        fn on_expand(&mut self, _board: &Board) {
+            self.events.push("expand");
+        }
+
+        fn on_solution(&mut self, _path: &LinkedList<Board>) {
+            self.events.push("solution");
+        }
+    }
+
+    #[test]
+    fn it_should_report_events_of_every_solver() {
+        let solvers: Vec<Box<dyn Algorithms>> = vec![
+            Box::new(UcsAlgorithms::new()),
+            Box::new(DfsAlgorithms::new()),
+            Box::new(AStarAlgorithms::with(Box::new(MahattanDistance::new()))),
+        ];
+        for mut solver in solvers {
+            let board = BoardParser::parse("1 2 3 4 5 6 0 7 8", true).unwrap();
+            let mut observer = (SearchStats::new(), Trace::default());
+            let path = solver.solve_with(board, &mut observer).unwrap();
+
+            let (stats, trace) = observer;
+            assert_eq!(stats.solution_length, Some(path.len() - 1));
+            assert!(stats.expanded >= 2);
+            assert!(stats.generated >= stats.expanded);
+            assert_eq!(trace.events.last(), Some(&"solution"));
+            assert_eq!(trace.events.len(), stats.expanded + 1);
+        }
+    }
+}
@@ -0,0 +1,268 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use crate::board::moves::Move;
+
+/// Longest move sequences [`MovePruning::default`] compares, which forbids a few hundred of them
+/// while taking a fraction of a second to generate.
+const DEFAULT_LENGTH: usize = 10;
+
+type Cell = (i32, i32);
+
+/// What a sequence of moves does wherever it is played: where it leaves the empty tile and
+/// where every tile it displaced came from, relative to the cell the empty tile started on.
+#[derive(PartialEq, Eq, Hash, Clone)]
+struct Effect {
+    blank: Cell,
+    moved: BTreeMap<Cell, Cell>,
+}
+
+/// Rows and columns a sequence takes the empty tile through, relative to where it started.
+#[derive(Clone, Copy)]
+struct Bounds {
+    top: i32,
+    bottom: i32,
+    left: i32,
+    right: i32,
+}
+
+impl Bounds {
+    fn contains(&self, other: &Bounds) -> bool {
+        self.top <= other.top
+            && other.bottom <= self.bottom
+            && self.left <= other.left
+            && other.right <= self.right
+    }
+}
+
+/// A sequence being explored, with its effect and bounds.
+#[derive(Clone)]
+struct Sequence {
+    moves: Vec<usize>,
+    effect: Effect,
+    bounds: Bounds,
+}
+
+impl Sequence {
+    fn push(&self, step: usize) -> Self {
+        let (row, col) = self.effect.blank;
+        let next = match Move::ALL[step] {
+            Move::Up => (row - 1, col),
+            Move::Down => (row + 1, col),
+            Move::Left => (row, col - 1),
+            Move::Right => (row, col + 1),
+        };
+        let mut moved = self.effect.moved.clone();
+        // the tile on `next` slides onto the empty tile's cell
+        let tile = moved.remove(&next).unwrap_or(next);
+        if tile != self.effect.blank {
+            moved.insert(self.effect.blank, tile);
+        }
+        let mut moves = self.moves.clone();
+        moves.push(step);
+        Self {
+            moves,
+            effect: Effect { blank: next, moved },
+            bounds: Bounds {
+                top: self.bounds.top.min(next.0),
+                bottom: self.bounds.bottom.max(next.0),
+                left: self.bounds.left.min(next.1),
+                right: self.bounds.right.max(next.1),
+            },
+        }
+    }
+}
+
+/// Finite-state machine that forbids move sequences known to be redundant, letting
+/// depth-first searches skip duplicate boards without remembering any of them.
+///
+/// Every sequence up to a length is played on an unbounded board; a sequence is forbidden when
+/// a shorter one, or one of the same length coming first in [`Move::ALL`] order, has the same
+/// effect without taking the empty tile anywhere the first does not go, so it can be played
+/// wherever the first can. That covers undoing the last move as well as longer transpositions.
+/// The forbidden sequences are compiled into an automaton that recognises them as substrings,
+/// whose state a search carries along its path.
+///
+/// Among the paths reaching a board, the shortest one first in move order is never pruned, so
+/// tree searches such as [`crate::ParallelIdaStarAlgorithms`] stay complete and optimal; they
+/// do however report a single path out of each set of equivalent ones. Searches that also drop
+/// the boards they have seen, like [`crate::DfsAlgorithms`], may reach a board first along a
+/// path whose continuations are pruned, and should not use it.
+#[derive(Debug, Clone)]
+pub struct MovePruning {
+    /// Next state for every state and move in [`Move::ALL`] order, `None` when the move
+    /// completes a forbidden sequence.
+    transitions: Vec<[Option<usize>; 4]>,
+    forbidden: usize,
+}
+
+impl MovePruning {
+    /// State of the automaton before any move is made.
+    pub const START: usize = 0;
+
+    /// Compares every sequence of at most `max_length` moves.
+    pub fn generate(max_length: usize) -> Self {
+        let forbidden = Self::forbidden_sequences(max_length);
+        Self {
+            forbidden: forbidden.len(),
+            transitions: Self::compile(&forbidden),
+        }
+    }
+
+    /// Number of forbidden sequences found.
+    pub fn forbidden(&self) -> usize {
+        self.forbidden
+    }
+
+    /// Number of states of the automaton.
+    pub fn states(&self) -> usize {
+        self.transitions.len()
+    }
+
+    /// State reached by playing `step` from `state`, `None` when the move is pruned.
+    pub fn next(&self, state: usize, step: Move) -> Option<usize> {
+        self.transitions[state][Self::index_of(step)]
+    }
+
+    /// Whether no move of `moves` is pruned when playing them in order from the start.
+    pub fn allows(&self, moves: &[Move]) -> bool {
+        moves
+            .iter()
+            .try_fold(Self::START, |state, step| self.next(state, *step))
+            .is_some()
+    }
+
+    fn index_of(step: Move) -> usize {
+        Move::ALL.iter().position(|other| *other == step).unwrap()
+    }
+
+    /// Explores sequences breadth-first, extending only those without a forbidden part, so
+    /// they come in order of length and then of moves.
+    fn forbidden_sequences(max_length: usize) -> Vec<Vec<usize>> {
+        let empty = Sequence {
+            moves: Vec::new(),
+            effect: Effect {
+                blank: (0, 0),
+                moved: BTreeMap::new(),
+            },
+            bounds: Bounds {
+                top: 0,
+                bottom: 0,
+                left: 0,
+                right: 0,
+            },
+        };
+        let mut seen: HashMap<Effect, Vec<Bounds>> =
+            HashMap::from([(empty.effect.clone(), vec![empty.bounds])]);
+        let mut forbidden: Vec<Vec<usize>> = Vec::new();
+        let mut known: HashSet<Vec<usize>> = HashSet::new();
+        let mut queue = VecDeque::from([empty]);
+        while let Some(sequence) = queue.pop_front() {
+            if sequence.moves.len() == max_length {
+                continue;
+            }
+            for step in 0..Move::ALL.len() {
+                let next = sequence.push(step);
+                // the prefix is allowed, so only a suffix can already be forbidden
+                if (1..next.moves.len()).any(|start| known.contains(&next.moves[start..])) {
+                    continue;
+                }
+                let earlier = seen.entry(next.effect.clone()).or_default();
+                if earlier.iter().any(|bounds| next.bounds.contains(bounds)) {
+                    known.insert(next.moves.clone());
+                    forbidden.push(next.moves);
+                    continue;
+                }
+                earlier.push(next.bounds);
+                queue.push_back(next);
+            }
+        }
+        forbidden
+    }
+
+    /// Builds the Aho-Corasick automaton of `forbidden`, with the moves completing one of them
+    /// left out.
+    fn compile(forbidden: &[Vec<usize>]) -> Vec<[Option<usize>; 4]> {
+        let mut goto: Vec<[Option<usize>; 4]> = vec![[None; 4]];
+        let mut terminal = vec![false];
+        for sequence in forbidden {
+            let mut state = Self::START;
+            for &step in sequence {
+                state = match goto[state][step] {
+                    Some(next) => next,
+                    None => {
+                        goto.push([None; 4]);
+                        terminal.push(false);
+                        goto[state][step] = Some(goto.len() - 1);
+                        goto.len() - 1
+                    }
+                };
+            }
+            terminal[state] = true;
+        }
+
+        // complete the transitions breadth-first along the failure links
+        let mut transitions = vec![[Self::START; 4]; goto.len()];
+        let mut fail = vec![Self::START; goto.len()];
+        let mut queue = VecDeque::from([Self::START]);
+        while let Some(state) = queue.pop_front() {
+            for step in 0..4 {
+                match goto[state][step] {
+                    Some(next) => {
+                        if state != Self::START {
+                            fail[next] = transitions[fail[state]][step];
+                        }
+                        terminal[next] |= terminal[fail[next]];
+                        transitions[state][step] = next;
+                        queue.push_back(next);
+                    }
+                    None if state != Self::START => {
+                        transitions[state][step] = transitions[fail[state]][step];
+                    }
+                    None => {}
+                }
+            }
+        }
+        transitions
+            .into_iter()
+            .map(|row| row.map(|next| (!terminal[next]).then_some(next)))
+            .collect()
+    }
+}
+
+impl Default for MovePruning {
+    fn default() -> Self {
+        Self::generate(DEFAULT_LENGTH)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::board::moves::Move::{self, Down, Left, Right, Up};
+
+    use super::MovePruning;
+
+    #[test]
+    fn it_should_forbid_undoing_the_last_move() {
+        let pruning = MovePruning::generate(2);
+        assert_eq!(pruning.forbidden(), 4);
+        for step in Move::ALL {
+            assert!(pruning.allows(&[step]));
+            assert!(!pruning.allows(&[step, step.inverse()]));
+            assert!(pruning.allows(&[step, step]));
+        }
+    }
+
+    #[test]
+    fn it_should_forbid_one_way_around_a_block() {
+        let pruning = MovePruning::generate(8);
+        // half way and a quarter around a 2x2 block either way does the same
+        let clockwise = [Right, Down, Left, Up, Right, Down];
+        let anticlockwise = [Down, Right, Up, Left, Down, Right];
+        assert!(pruning.allows(&anticlockwise));
+        assert!(!pruning.allows(&clockwise));
+        assert!(!pruning.allows(&[Left, Up, Right, Down, Left, Up]));
+        // forbidden sequences are caught anywhere in a path
+        assert!(!pruning.allows(&[Up, Up, Right, Down, Left, Up, Right, Down]));
+        assert!(pruning.allows(&[Up, Up, Left, Left, Down, Right, Right, Down]));
+    }
+}
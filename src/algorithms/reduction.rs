@@ -0,0 +1,443 @@
+use std::collections::{LinkedList, VecDeque};
+
+use crate::{
+    board::{
+        board::{Board, BoardManager},
+        cell::Tile,
+        moves::Move,
+    },
+    AStarAlgorithms, BoardConfigBuilder, GoalLayout, PuzzleError,
+};
+
+use super::{
+    informed_search::heuristic::mahattan_distance::MahattanDistance,
+    observer::{NoopObserver, SearchObserver},
+    Algorithms,
+};
+
+/// Size of the part of the board left to an exact search once the rest is in place.
+const FINAL_SIZE: usize = 3;
+
+/// Solves boards of any size the way people do: the top row and the left column are put in
+/// place, the board is reduced to `(n-1)×(n-1)`, and the last `3×3` is solved by A*.
+///
+/// Tiles are brought to their cell by the shortest sequence of moves that leaves the placed
+/// tiles alone. The last two tiles of a row or column cannot be placed one after the other, so
+/// they are set up next to their cells and turned in with a two-move macro. Solutions are far
+/// from optimal but take well under a second even for `16×16` boards.
+pub struct ReductionAlgorithms;
+
+impl ReductionAlgorithms {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Moves of the empty tile that solve `board`. They are replayed on the board before being
+    /// returned, so a list that does not reach the goal is reported as
+    /// [`PuzzleError::NoSolution`] rather than returned.
+    pub fn moves(&self, board: &Board) -> Result<Vec<Move>, PuzzleError> {
+        self.moves_with(board, &NoopObserver)
+    }
+
+    /// [`Self::moves`], giving up with [`PuzzleError::Cancelled`] once `observer` is cancelled.
+    fn moves_with(
+        &self,
+        board: &Board,
+        observer: &dyn SearchObserver,
+    ) -> Result<Vec<Move>, PuzzleError> {
+        let n = BoardManager::size_of(board) as usize;
+        let tiles = BoardManager::tiles_of(board);
+        let goal_state = BoardManager::goal_of(board);
+        if !BoardManager::is_solvable(n as u8, tiles, goal_state) {
+            return Err(PuzzleError::Unsolvable);
+        }
+        let values = |tiles: &[Tile]| tiles.iter().map(Tile::get_value).collect::<Vec<u8>>();
+
+        // the reduction finishes with the empty tile in the bottom-right corner, so it aims at
+        // the goal with the empty tile walked there and walks it back at the end
+        let mut target = Grid::new(n, values(goal_state));
+        while target.blank % n != n - 1 {
+            target.play(Move::Right);
+        }
+        while target.blank / n != n - 1 {
+            target.play(Move::Down);
+        }
+
+        let mut grid = Grid::new(n, values(tiles));
+        let mut offset = 0;
+        while n - offset > FINAL_SIZE {
+            grid.reduce(&target.cells, offset, observer)?;
+            offset += 1;
+        }
+        grid.finish(&target.cells, offset, observer)?;
+        grid.moves
+            .extend(target.moves.iter().rev().map(Move::inverse));
+
+        let mut replay = Grid::new(n, values(tiles));
+        for step in &grid.moves {
+            replay.try_play(*step).ok_or(PuzzleError::NoSolution)?;
+        }
+        if replay.cells != values(goal_state) {
+            return Err(PuzzleError::NoSolution);
+        }
+        Ok(grid.moves)
+    }
+}
+
+impl Default for ReductionAlgorithms {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Algorithms for ReductionAlgorithms {
+    fn solve_with(
+        &mut self,
+        initial_state: Board,
+        observer: &mut dyn SearchObserver,
+    ) -> Option<LinkedList<Board>> {
+        let moves = self.moves_with(&initial_state, observer).ok()?;
+        let mut solutions = LinkedList::new();
+        let mut current = initial_state;
+        for step in moves {
+            let next = BoardManager::apply_move(&current, step)?;
+            solutions.push_back(current);
+            current = next;
+        }
+        solutions.push_back(current);
+        observer.on_solution(&solutions);
+        Some(solutions)
+    }
+}
+
+/// Lets the exact search of the last corner be cancelled without passing on its events, which
+/// are about the relabelled corner rather than the board being solved.
+struct CancelOnly<'a>(&'a dyn SearchObserver);
+
+impl SearchObserver for CancelOnly<'_> {
+    fn is_cancelled(&self) -> bool {
+        self.0.is_cancelled()
+    }
+}
+
+/// Fails with [`PuzzleError::Cancelled`] once `observer` is cancelled. The reduction expands no
+/// boards of the search tree, so there is no work to report.
+fn check_cancelled(observer: &dyn SearchObserver) -> Result<(), PuzzleError> {
+    if observer.is_cancelled() {
+        return Err(PuzzleError::Cancelled {
+            expanded: 0,
+            generated: 0,
+        });
+    }
+    Ok(())
+}
+
+struct Grid {
+    n: usize,
+    cells: Vec<u8>,
+    blank: usize,
+    locked: Vec<bool>,
+    moves: Vec<Move>,
+}
+
+impl Grid {
+    fn new(n: usize, cells: Vec<u8>) -> Self {
+        let blank = cells.iter().position(|value| *value == 0).unwrap();
+        Self {
+            n,
+            cells,
+            blank,
+            locked: vec![false; n * n],
+            moves: Vec::new(),
+        }
+    }
+
+    fn try_play(&mut self, step: Move) -> Option<()> {
+        let to = step.apply(self.blank, self.n)?;
+        self.cells.swap(self.blank, to);
+        self.blank = to;
+        self.moves.push(step);
+        Some(())
+    }
+
+    fn play(&mut self, step: Move) {
+        self.try_play(step).unwrap();
+    }
+
+    /// Moves the tile `value` to `to` with the fewest moves that keep the empty tile off
+    /// locked cells, searching over the positions of both the tile and the empty tile.
+    fn place(
+        &mut self,
+        value: u8,
+        to: usize,
+        observer: &dyn SearchObserver,
+    ) -> Result<(), PuzzleError> {
+        check_cancelled(observer)?;
+        let cells = self.n * self.n;
+        let from = self.cells.iter().position(|cell| *cell == value).unwrap();
+        let start = from * cells + self.blank;
+        let mut previous: Vec<Option<(usize, Move)>> = vec![None; cells * cells];
+        let mut queue = VecDeque::from([start]);
+        let mut visited = vec![false; cells * cells];
+        visited[start] = true;
+        while let Some(state) = queue.pop_front() {
+            let (tile, blank) = (state / cells, state % cells);
+            if tile == to {
+                let mut steps = Vec::new();
+                let mut state = state;
+                while let Some((before, step)) = previous[state] {
+                    steps.push(step);
+                    state = before;
+                }
+                steps.into_iter().rev().for_each(|step| self.play(step));
+                return Ok(());
+            }
+            for step in Move::ALL {
+                let Some(next_blank) = step.apply(blank, self.n) else {
+                    continue;
+                };
+                if self.locked[next_blank] {
+                    continue;
+                }
+                let next_tile = if next_blank == tile { blank } else { tile };
+                let next = next_tile * cells + next_blank;
+                if !visited[next] {
+                    visited[next] = true;
+                    previous[next] = Some((state, step));
+                    queue.push_back(next);
+                }
+            }
+        }
+        Err(PuzzleError::NoSolution)
+    }
+
+    /// Moves the empty tile to `to` without crossing locked cells.
+    fn route_blank(&mut self, to: usize) -> Result<(), PuzzleError> {
+        let mut previous: Vec<Option<(usize, Move)>> = vec![None; self.n * self.n];
+        let mut visited = vec![false; self.n * self.n];
+        let mut queue = VecDeque::from([self.blank]);
+        visited[self.blank] = true;
+        while let Some(idx) = queue.pop_front() {
+            if idx == to {
+                let mut steps = Vec::new();
+                let mut idx = idx;
+                while let Some((before, step)) = previous[idx] {
+                    steps.push(step);
+                    idx = before;
+                }
+                steps.into_iter().rev().for_each(|step| self.play(step));
+                return Ok(());
+            }
+            for step in Move::ALL {
+                match step.apply(idx, self.n) {
+                    Some(next) if !self.locked[next] && !visited[next] => {
+                        visited[next] = true;
+                        previous[next] = Some((idx, step));
+                        queue.push_back(next);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Err(PuzzleError::NoSolution)
+    }
+
+    /// Puts the tiles of `target` at cells `a` and `b`, the last two of a row or column.
+    ///
+    /// The tile of `b` is placed at `a` and the tile of `a` next to it at `aside`, then `turn`
+    /// moves the empty tile from `b` through `a` to `aside`, sliding both into place. The tile
+    /// of `a` waits in the far corner `park` meanwhile: left at `b` it could only leave by
+    /// trapping the empty tile there.
+    fn place_pair(
+        &mut self,
+        target: &[u8],
+        (a, b, aside, park): (usize, usize, usize, usize),
+        turn: [Move; 2],
+        observer: &dyn SearchObserver,
+    ) -> Result<(), PuzzleError> {
+        if self.cells[a] != target[a] || self.cells[b] != target[b] {
+            self.place(target[a], park, observer)?;
+            self.locked[park] = true;
+            self.place(target[b], a, observer)?;
+            self.locked[a] = true;
+            self.locked[park] = false;
+            self.place(target[a], aside, observer)?;
+            self.locked[aside] = true;
+            self.route_blank(b)?;
+            turn.into_iter().for_each(|step| self.play(step));
+            self.locked[aside] = false;
+        }
+        self.locked[a] = true;
+        self.locked[b] = true;
+        Ok(())
+    }
+
+    /// Places row `offset` and column `offset`, leaving the board below and to the right of
+    /// them to solve.
+    fn reduce(
+        &mut self,
+        target: &[u8],
+        offset: usize,
+        observer: &dyn SearchObserver,
+    ) -> Result<(), PuzzleError> {
+        let n = self.n;
+        let cell = |row: usize, column: usize| row * n + column;
+        for column in offset..n - 2 {
+            self.place(target[cell(offset, column)], cell(offset, column), observer)?;
+            self.locked[cell(offset, column)] = true;
+        }
+        self.place_pair(
+            target,
+            (
+                cell(offset, n - 2),
+                cell(offset, n - 1),
+                cell(offset + 1, n - 2),
+                cell(n - 1, offset),
+            ),
+            [Move::Left, Move::Down],
+            observer,
+        )?;
+        for row in offset + 1..n - 2 {
+            self.place(target[cell(row, offset)], cell(row, offset), observer)?;
+            self.locked[cell(row, offset)] = true;
+        }
+        self.place_pair(
+            target,
+            (
+                cell(n - 2, offset),
+                cell(n - 1, offset),
+                cell(n - 2, offset + 1),
+                cell(offset + 1, n - 1),
+            ),
+            [Move::Up, Move::Right],
+            observer,
+        )
+    }
+
+    /// Solves the bottom-right corner from `offset` on, relabelled as a board of its own, with
+    /// A* and the Manhattan distance.
+    fn finish(
+        &mut self,
+        target: &[u8],
+        offset: usize,
+        observer: &dyn SearchObserver,
+    ) -> Result<(), PuzzleError> {
+        check_cancelled(observer)?;
+        let n = self.n;
+        let size = n - offset;
+        let region: Vec<usize> = (offset..n)
+            .flat_map(|row| (offset..n).map(move |column| row * n + column))
+            .collect();
+        // the target's empty tile is in the last cell of the region, as in a row-major goal
+        let mut labels = vec![0; n * n];
+        region
+            .iter()
+            .enumerate()
+            .filter(|(_, idx)| target[**idx] != 0)
+            .for_each(|(label, idx)| labels[target[*idx] as usize] = label as u8 + 1);
+        let tiles = region
+            .iter()
+            .map(|idx| Tile::with_value(labels[self.cells[*idx] as usize]))
+            .collect();
+        let config = BoardConfigBuilder::builder()
+            .layout(size as u8, GoalLayout::RowMajor)
+            .with_informed()
+            .build()?;
+        let path = AStarAlgorithms::with(Box::new(MahattanDistance::new()))
+            .solve_with(Board::with_tiles(config, tiles), &mut CancelOnly(observer))
+            .ok_or(PuzzleError::NoSolution)?;
+        path.iter()
+            .zip(path.iter().skip(1))
+            .map(|(from, to)| BoardManager::move_of(from, to).ok_or(PuzzleError::NoSolution))
+            .collect::<Result<Vec<Move>, PuzzleError>>()?
+            .into_iter()
+            .for_each(|step| self.play(step));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use crate::{
+        board::{board::BoardManager, cell::Tile, scrambled},
+        Algorithms, Board, GoalLayout, PuzzleError, Scramble, SearchObserver, SearchStats,
+    };
+
+    use super::ReductionAlgorithms;
+
+    #[test]
+    fn it_should_solve_boards_of_any_size_and_layout() {
+        for layout in [
+            GoalLayout::RowMajor,
+            GoalLayout::BlankFirst,
+            GoalLayout::Snail,
+        ] {
+            for n in [2, 3, 4, 5, 7, 10] {
+                let board = scrambled(n, layout, Scramble::Uniform, n as u64);
+                let moves = ReductionAlgorithms::new().moves(&board).unwrap();
+                let solved = moves.iter().fold(board, |board, step| {
+                    BoardManager::apply_move(&board, *step).unwrap()
+                });
+                assert!(solved.match_goal(), "{}x{} {}", n, n, layout);
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_return_the_boards_along_the_moves() {
+        let board = scrambled(12, GoalLayout::RowMajor, Scramble::Uniform, 1);
+        let moves = ReductionAlgorithms::new().moves(&board).unwrap();
+        let path = ReductionAlgorithms::new().solve(board.clone()).unwrap();
+        assert_eq!(path.len(), moves.len() + 1);
+        assert_eq!(path.front(), Some(&board));
+        assert!(path.back().unwrap().match_goal());
+    }
+
+    /// Cancels the reduction the given number of checks in, as it expands no boards to count.
+    struct CancelOnCheck(Cell<usize>);
+
+    impl SearchObserver for CancelOnCheck {
+        fn is_cancelled(&self) -> bool {
+            self.0.set(self.0.get().saturating_sub(1));
+            self.0.get() == 0
+        }
+    }
+
+    #[test]
+    fn it_should_stop_when_cancelled_mid_search() {
+        let board = scrambled(12, GoalLayout::RowMajor, Scramble::Uniform, 1);
+        let observer = CancelOnCheck(Cell::new(20));
+        assert_eq!(
+            ReductionAlgorithms::new().moves_with(&board, &observer),
+            Err(PuzzleError::Cancelled {
+                expanded: 0,
+                generated: 0
+            })
+        );
+        assert_eq!(observer.0.get(), 0);
+        let mut observer = (SearchStats::new(), CancelOnCheck(Cell::new(20)));
+        assert!(ReductionAlgorithms::new()
+            .solve_with(board, &mut observer)
+            .is_none());
+        assert_eq!(observer.0.solution_length, None);
+    }
+
+    #[test]
+    fn it_should_reject_unsolvable_boards() {
+        let solved = scrambled(4, GoalLayout::RowMajor, Scramble::Uniform, 1);
+        let config = BoardManager::config_of(&solved).clone();
+        let mut tiles = BoardManager::tiles_of(&solved).to_vec();
+        let swap: Vec<usize> = (0..tiles.len())
+            .filter(|idx| tiles[*idx] != Tile::Empty)
+            .take(2)
+            .collect();
+        tiles.swap(swap[0], swap[1]);
+        let board = Board::with_tiles(config, tiles);
+        assert_eq!(
+            ReductionAlgorithms::new().moves(&board),
+            Err(PuzzleError::Unsolvable)
+        );
+    }
+}
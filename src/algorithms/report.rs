@@ -0,0 +1,139 @@
+use std::{collections::LinkedList, fmt::Display};
+
+use crate::{
+    board::{board::BoardManager, moves::Move},
+    Board,
+};
+
+use super::{
+    informed_search::{
+        heuristic::{
+            gaschnig::GaschnigDistance, inversion_distance::InversionDistance,
+            mahattan_distance::MahattanDistance, misplaced_tiles::MisplacedTiles,
+            out_of_line::OutOfRowAndColumn, HeuristicFn,
+        },
+        AStarAlgorithms,
+    },
+    observer::SearchStats,
+    Algorithms,
+};
+
+/// Machine-readable summary of a solution: the starting board and the moves of the empty tile
+/// that bring it to the goal.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolveReport {
+    pub initial: Board,
+    pub moves: Vec<Move>,
+    pub length: usize,
+}
+
+impl SolveReport {
+    /// Builds the report from the boards returned by [`crate::Algorithms::solve`], or `None`
+    /// when the path is empty or two consecutive boards are not one move apart.
+    pub fn from_path(path: &LinkedList<Board>) -> Option<Self> {
+        let initial = path.front()?.clone();
+        let moves = path
+            .iter()
+            .zip(path.iter().skip(1))
+            .map(|(from, to)| BoardManager::move_of(from, to))
+            .collect::<Option<Vec<Move>>>()?;
+
+        Some(Self {
+            initial,
+            length: moves.len(),
+            moves,
+        })
+    }
+}
+
+/// Work A* does to solve one board under each of several heuristics.
+#[derive(Debug, Clone)]
+pub struct HeuristicComparison {
+    pub runs: Vec<(String, SearchStats)>,
+}
+
+impl HeuristicComparison {
+    /// Solves `board` with A* once per named heuristic, in order.
+    pub fn run(board: &Board, heuristics: Vec<(&str, Box<dyn HeuristicFn>)>) -> Self {
+        let runs = heuristics
+            .into_iter()
+            .map(|(name, heuristic)| {
+                let mut stats = SearchStats::new();
+                AStarAlgorithms::with(heuristic).solve_with(board.clone(), &mut stats);
+                (name.to_string(), stats)
+            })
+            .collect();
+        Self { runs }
+    }
+
+    /// Compares the textbook heuristics, from the weakest to [`MahattanDistance`], and
+    /// [`InversionDistance`].
+    pub fn textbook(board: &Board) -> Self {
+        Self::run(
+            board,
+            vec![
+                ("misplaced tiles", Box::new(MisplacedTiles)),
+                ("gaschnig", Box::new(GaschnigDistance)),
+                ("out of row and column", Box::new(OutOfRowAndColumn::new())),
+                ("manhattan", Box::new(MahattanDistance::new())),
+                ("inversion", Box::new(InversionDistance::new())),
+            ],
+        )
+    }
+}
+
+impl Display for HeuristicComparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let width = self
+            .runs
+            .iter()
+            .map(|(name, _)| name.len())
+            .max()
+            .unwrap_or(0)
+            .max("heuristic".len());
+        writeln!(
+            f,
+            "{:<width$}  {:>10}  {:>10}  {:>6}",
+            "heuristic", "expanded", "generated", "length"
+        )?;
+        for (name, stats) in &self.runs {
+            let length = stats
+                .solution_length
+                .map_or("-".to_string(), |length| length.to_string());
+            writeln!(
+                f,
+                "{:<width$}  {:>10}  {:>10}  {:>6}",
+                name, stats.expanded, stats.generated, length
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{board::scrambled, GoalLayout, Scramble};
+
+    use super::HeuristicComparison;
+
+    #[test]
+    fn it_should_compare_the_textbook_heuristics() {
+        let board = scrambled(3, GoalLayout::RowMajor, Scramble::Distance(16), 3);
+        let comparison = HeuristicComparison::textbook(&board);
+        assert_eq!(comparison.runs.len(), 5);
+        let expanded = |name: &str| {
+            let (_, stats) = comparison.runs.iter().find(|(run, _)| run == name).unwrap();
+            assert_eq!(stats.solution_length, Some(16));
+            stats.expanded
+        };
+        assert!(expanded("manhattan") < expanded("out of row and column"));
+        assert!(expanded("out of row and column") < expanded("misplaced tiles"));
+        assert!(expanded("manhattan") < expanded("gaschnig"));
+        expanded("inversion");
+
+        let table = comparison.to_string();
+        assert_eq!(table.lines().count(), 6);
+        assert!(table.lines().nth(4).unwrap().starts_with("manhattan"));
+    }
+}
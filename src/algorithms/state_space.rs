@@ -0,0 +1,285 @@
+use std::collections::VecDeque;
+
+use crate::{
+    board::{board::BoardManager, cell::Tile},
+    error::{PermutationError, PuzzleError},
+};
+
+/// Largest number of cells [`StateSpace::explore`] accepts; 10! distances still fit in a few
+/// megabytes.
+const MAX_CELLS: usize = 10;
+
+/// The whole state space of a `rows` x `cols` puzzle, explored by a breadth-first search
+/// from its goal.
+///
+/// The distance of every permutation is kept in a table indexed by its Lehmer rank, so the
+/// optimal solution length of any board of that shape is a single lookup afterwards.
+pub struct StateSpace {
+    rows: usize,
+    cols: usize,
+    goal: Vec<Tile>,
+    /// Distance of every permutation from the goal, [`StateSpace::UNREACHABLE`] for those on
+    /// the other parity.
+    distances: Vec<u8>,
+    /// Number of states at each distance.
+    distribution: Vec<usize>,
+}
+
+impl StateSpace {
+    pub const UNREACHABLE: u8 = u8::MAX;
+
+    /// Explores the space around the goal whose tiles run in row-major order with the empty
+    /// tile last.
+    pub fn explore(rows: u8, cols: u8) -> Result<Self, PuzzleError> {
+        let cells = rows as usize * cols as usize;
+        let goal: Vec<Tile> = (1..cells as u8).chain([0]).map(Tile::with_value).collect();
+        Self::explore_from(rows, cols, &goal)
+    }
+
+    pub fn explore_from(rows: u8, cols: u8, goal_state: &[Tile]) -> Result<Self, PuzzleError> {
+        let (rows, cols) = (rows as usize, cols as usize);
+        let cells = rows * cols;
+        if rows == 0 || cols == 0 || !(2..=MAX_CELLS).contains(&cells) {
+            return Err(PuzzleError::InvalidSize(cells));
+        }
+        if goal_state.len() != cells {
+            return Err(PermutationError::LengthMismatch {
+                expected: cells,
+                found: goal_state.len(),
+            }
+            .into());
+        }
+        let goal: Vec<u8> = goal_state.iter().map(Tile::get_value).collect();
+        Self::check_permutation(&goal)?;
+
+        let mut distances = vec![Self::UNREACHABLE; (1..=cells).product()];
+        let mut distribution = vec![1];
+        distances[Self::rank(&goal)] = 0;
+        let mut queue = VecDeque::from([goal]);
+        while let Some(state) = queue.pop_front() {
+            let distance = distances[Self::rank(&state)];
+            let blank = state.iter().position(|value| *value == 0).unwrap();
+            for next in Self::adjacent(blank, rows, cols) {
+                let mut neighbor = state.clone();
+                neighbor.swap(blank, next);
+                let rank = Self::rank(&neighbor);
+                if distances[rank] != Self::UNREACHABLE {
+                    continue;
+                }
+                distances[rank] = distance + 1;
+                match distribution.get_mut(distance as usize + 1) {
+                    Some(count) => *count += 1,
+                    None => distribution.push(1),
+                }
+                queue.push_back(neighbor);
+            }
+        }
+
+        Ok(Self {
+            rows,
+            cols,
+            goal: goal_state.to_vec(),
+            distances,
+            distribution,
+        })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn goal(&self) -> &[Tile] {
+        &self.goal
+    }
+
+    /// Number of states reachable from the goal.
+    pub fn states(&self) -> usize {
+        self.distribution.iter().sum()
+    }
+
+    /// Number of moves needed by the hardest states.
+    pub fn max_distance(&self) -> usize {
+        self.distribution.len() - 1
+    }
+
+    /// Number of states at each distance from the goal.
+    pub fn distribution(&self) -> &[usize] {
+        &self.distribution
+    }
+
+    /// Average optimal solution length over every reachable state.
+    pub fn average_distance(&self) -> f64 {
+        let total: usize = self
+            .distribution
+            .iter()
+            .enumerate()
+            .map(|(distance, count)| distance * count)
+            .sum();
+        total as f64 / self.states() as f64
+    }
+
+    /// The states farthest from the goal, in rank order.
+    pub fn antipodes(&self) -> Vec<Vec<Tile>> {
+        let max = self.max_distance() as u8;
+        self.distances
+            .iter()
+            .enumerate()
+            .filter(|(_, distance)| **distance == max)
+            .map(|(rank, _)| {
+                Self::unrank(rank, self.rows * self.cols)
+                    .into_iter()
+                    .map(Tile::with_value)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Distance of every permutation from the goal, indexed by [`StateSpace::rank_of`].
+    pub fn table(&self) -> &[u8] {
+        &self.distances
+    }
+
+    /// Optimal solution length of `tiles`, `None` if it cannot reach the goal or does not
+    /// have this shape.
+    pub fn distance_of(&self, tiles: &[Tile]) -> Option<usize> {
+        if tiles.len() != self.rows * self.cols {
+            return None;
+        }
+        let values: Vec<u8> = tiles.iter().map(Tile::get_value).collect();
+        Self::check_permutation(&values).ok()?;
+        match self.distances[Self::rank(&values)] {
+            Self::UNREACHABLE => None,
+            distance => Some(distance as usize),
+        }
+    }
+
+    /// Checks that `values` holds every number of `0..values.len()` exactly once.
+    fn check_permutation(values: &[u8]) -> Result<(), PermutationError> {
+        let values: Vec<usize> = values.iter().map(|value| *value as usize).collect();
+        BoardManager::check_cells(values.len(), &values)
+    }
+
+    /// Lehmer rank of a permutation of `0..tiles.len()`.
+    pub fn rank_of(tiles: &[Tile]) -> usize {
+        let values: Vec<u8> = tiles.iter().map(Tile::get_value).collect();
+        Self::rank(&values)
+    }
+
+    fn rank(values: &[u8]) -> usize {
+        values.iter().enumerate().fold(0, |rank, (idx, value)| {
+            let smaller_after = values[idx + 1..].iter().filter(|v| *v < value).count();
+            rank * (values.len() - idx) + smaller_after
+        })
+    }
+
+    fn unrank(mut rank: usize, len: usize) -> Vec<u8> {
+        let mut digits = vec![0; len];
+        for (radix, digit) in (1..=len).zip(digits.iter_mut().rev()) {
+            *digit = rank % radix;
+            rank /= radix;
+        }
+        let mut remaining: Vec<u8> = (0..len as u8).collect();
+        digits
+            .into_iter()
+            .map(|digit| remaining.remove(digit))
+            .collect()
+    }
+
+    fn adjacent(idx: usize, rows: usize, cols: usize) -> impl Iterator<Item = usize> {
+        let (row, col) = (idx / cols, idx % cols);
+        [
+            (col > 0).then(|| idx - 1),
+            (col + 1 < cols).then(|| idx + 1),
+            (row > 0).then(|| idx - cols),
+            (row + 1 < rows).then(|| idx + cols),
+        ]
+        .into_iter()
+        .flatten()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{board::board::BoardManager, board::parser::BoardParser, PuzzleError, Tile};
+
+    use super::StateSpace;
+
+    #[test]
+    fn it_should_rank_and_unrank_permutations() {
+        for rank in 0..24 {
+            let tiles: Vec<Tile> = StateSpace::unrank(rank, 4)
+                .into_iter()
+                .map(Tile::with_value)
+                .collect();
+            assert_eq!(StateSpace::rank_of(&tiles), rank);
+        }
+    }
+
+    #[test]
+    fn it_should_match_the_known_results() {
+        // (rows, cols, reachable states, hardest distance, antipodes)
+        for (rows, cols, states, max, antipodes) in [
+            (2, 2, 12, 6, 1),
+            (2, 3, 360, 21, 1),
+            (2, 4, 20160, 36, 1),
+            (3, 3, 181440, 31, 2),
+        ] {
+            let space = StateSpace::explore(rows, cols).unwrap();
+            assert_eq!(space.states(), states);
+            assert_eq!(space.max_distance(), max);
+            assert_eq!(space.antipodes().len(), antipodes);
+            assert_eq!(space.distribution()[0], 1);
+            assert!(space
+                .antipodes()
+                .iter()
+                .all(|tiles| space.distance_of(tiles) == Some(max)));
+        }
+    }
+
+    #[test]
+    fn it_should_look_up_optimal_lengths() {
+        let space = StateSpace::explore(3, 3).unwrap();
+        assert!((space.average_distance() - 21.97).abs() < 0.01);
+        assert_eq!(space.antipodes()[0].len(), 9);
+
+        let board = BoardParser::parse("8 6 7 2 5 4 3 0 1", false).unwrap();
+        assert_eq!(space.distance_of(BoardManager::tiles_of(&board)), Some(31));
+        let (_, swapped) = BoardParser::parse_tiles("1 2 3 4 5 6 8 7 0").unwrap();
+        let swapped: Vec<Tile> = swapped.into_iter().map(Tile::with_value).collect();
+        assert_eq!(space.distance_of(&swapped), None);
+        assert_eq!(space.distance_of(&swapped[..8]), None);
+    }
+
+    #[test]
+    fn it_should_explore_from_other_goals() {
+        let goal: Vec<Tile> = [0, 1, 2, 3, 4, 5]
+            .into_iter()
+            .map(Tile::with_value)
+            .collect();
+        let space = StateSpace::explore_from(2, 3, &goal).unwrap();
+        assert_eq!(space.states(), 360);
+        assert_eq!(space.distance_of(&goal), Some(0));
+        assert_eq!(space.goal(), goal);
+    }
+
+    #[test]
+    fn it_should_reject_spaces_too_large_to_enumerate() {
+        assert!(matches!(
+            StateSpace::explore(4, 4),
+            Err(PuzzleError::InvalidSize(16))
+        ));
+        assert!(matches!(
+            StateSpace::explore(1, 1),
+            Err(PuzzleError::InvalidSize(1))
+        ));
+        let goal: Vec<Tile> = [1, 2, 3, 3].into_iter().map(Tile::with_value).collect();
+        assert!(matches!(
+            StateSpace::explore_from(2, 2, &goal),
+            Err(PuzzleError::InvalidPermutation(_))
+        ));
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::LinkedList;
+
+use crate::{board::cell::Tile, Board};
+
+use super::observer::SearchObserver;
+
+/// Outcome of advancing a search by one expansion.
+#[derive(Debug, Clone)]
+pub enum Step {
+    /// The board was expanded and its unseen neighbours joined the frontier.
+    Expanded(Board),
+    /// The board was taken from the frontier but had already been closed.
+    Skipped(Board),
+    /// The goal was reached through this path.
+    Solved(LinkedList<Board>),
+    /// The frontier ran out without reaching the goal.
+    Exhausted,
+}
+
+/// A search that the caller advances one expansion at a time, inspecting the frontier and the
+/// closed set in between. Every stepper is also an [`Iterator`] over its steps.
+pub trait SearchStepper {
+    fn step(&mut self, observer: &mut dyn SearchObserver) -> Step;
+
+    /// Boards waiting to be expanded, next one first.
+    fn frontier(&self) -> Vec<&Board>;
+
+    /// Tiles of every board that has already been expanded.
+    fn closed(&self) -> Vec<&[Tile]>;
+
+    /// Whether the search has been solved or exhausted.
+    fn is_finished(&self) -> bool;
+
+    /// Steps until the search finishes or `observer` cancels it, returning the solution if any.
+    fn run(&mut self, observer: &mut dyn SearchObserver) -> Option<LinkedList<Board>> {
+        loop {
+            if observer.is_cancelled() {
+                return None;
+            }
+            match self.step(observer) {
+                Step::Solved(path) => return Some(path),
+                Step::Exhausted => return None,
+                Step::Expanded(_) | Step::Skipped(_) => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        algorithms::{
+            informed_search::heuristic::mahattan_distance::MahattanDistance, observer::SearchStats,
+        },
+        board::{board::BoardManager, parser::BoardParser},
+        AStarAlgorithms, DfsAlgorithms, UcsAlgorithms,
+    };
+
+    use super::{SearchStepper, Step};
+
+    #[test]
+    fn it_should_expose_the_frontier_between_steps() {
+        let board = BoardParser::parse("1 2 3 4 5 6 7 0 8", false).unwrap();
+        let mut stepper = UcsAlgorithms::new().stepper(board.clone());
+        assert_eq!(stepper.frontier(), vec![&board]);
+        assert!(stepper.closed().is_empty());
+
+        let mut stats = SearchStats::new();
+        assert!(matches!(stepper.step(&mut stats), Step::Expanded(expanded) if expanded == board));
+        assert_eq!(stepper.frontier().len(), 3);
+        assert_eq!(stepper.closed(), vec![BoardManager::tiles_of(&board)]);
+        assert!(!stepper.is_finished());
+
+        let path = stepper.run(&mut stats).unwrap();
+        assert_eq!(path.len(), 2);
+        assert!(stepper.is_finished());
+        assert_eq!(stats.solution_length, Some(1));
+    }
+
+    #[test]
+    fn it_should_iterate_until_the_search_finishes() {
+        let board = BoardParser::parse("1 2 3 4 5 6 0 7 8", true).unwrap();
+        let astar = AStarAlgorithms::with(Box::new(MahattanDistance::new()));
+        let steps: Vec<Step> = astar.stepper(board.clone()).collect();
+        assert!(matches!(steps.last(), Some(Step::Solved(path)) if path.len() == 3));
+        assert!(steps[..steps.len() - 1]
+            .iter()
+            .all(|step| matches!(step, Step::Expanded(_) | Step::Skipped(_))));
+
+        let mut stepper = DfsAlgorithms::new().stepper(board);
+        stepper.step(&mut SearchStats::new());
+        let frontier = stepper.frontier();
+        assert_eq!(frontier.len(), 2);
+        assert!(stepper.next().is_some());
+    }
+}
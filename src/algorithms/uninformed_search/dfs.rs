@@ -1,44 +1,112 @@
 use std::collections::{BTreeMap, HashSet, LinkedList};
 
-use crate::{board::board::BoardManager, Algorithms, Board};
+use crate::{
+    algorithms::{
+        observer::{NoopObserver, SearchObserver},
+        stepper::{SearchStepper, Step},
+    },
+    board::{board::BoardManager, cell::Tile},
+    Algorithms, Board,
+};
 
-pub struct DfsAlgorithms {
+pub struct DfsAlgorithms;
+
+impl DfsAlgorithms {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn stepper(&self, initial_state: Board) -> DfsStepper {
+        DfsStepper::new(initial_state)
+    }
+}
+
+impl Default for DfsAlgorithms {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Algorithms for DfsAlgorithms {
+    fn solve_with(
+        &mut self,
+        initial_state: Board,
+        observer: &mut dyn SearchObserver,
+    ) -> Option<LinkedList<Board>> {
+        self.stepper(initial_state).run(observer)
+    }
+}
+
+pub struct DfsStepper {
+    stack: LinkedList<Board>,
     visited: HashSet<Board>,
     parents: BTreeMap<Board, Board>,
+    finished: bool,
 }
 
-impl DfsAlgorithms {
-    pub fn new() -> Self {
+impl DfsStepper {
+    pub fn new(initial_state: Board) -> Self {
+        let mut stack = LinkedList::new();
+        stack.push_back(initial_state);
         Self {
+            stack,
             visited: HashSet::new(),
             parents: BTreeMap::new(),
+            finished: false,
         }
     }
 }
 
-impl Algorithms for DfsAlgorithms {
-    fn solve(&mut self, initial_state: Board) -> Option<std::collections::LinkedList<Board>> {
-        let mut solutions: LinkedList<Board> = LinkedList::new();
-        let mut stack: LinkedList<Board> = LinkedList::new();
-        stack.push_back(initial_state);
-        while let Some(mut current) = stack.pop_back() {
-            if current.match_goal() {
-                solutions.push_front(current.clone());
-                while let Some(parent) = self.parents.get(&current) {
-                    solutions.push_front(parent.clone());
-                    current = parent.clone();
-                }
-                return Some(solutions);
+impl SearchStepper for DfsStepper {
+    fn step(&mut self, observer: &mut dyn SearchObserver) -> Step {
+        let Some(mut current) = self.stack.pop_back() else {
+            self.finished = true;
+            return Step::Exhausted;
+        };
+        if current.match_goal() {
+            let mut solutions: LinkedList<Board> = LinkedList::new();
+            solutions.push_front(current.clone());
+            while let Some(parent) = self.parents.get(&current) {
+                solutions.push_front(parent.clone());
+                current = parent.clone();
             }
-            self.visited.insert(current.clone());
-            BoardManager::neigbors_of(&current)
-                .into_iter()
-                .filter(|board| !self.visited.contains(board))
-                .for_each(|board| {
-                    self.parents.insert(board.clone(), current.clone());
-                    stack.push_back(board);
-                });
+            observer.on_solution(&solutions);
+            self.finished = true;
+            return Step::Solved(solutions);
         }
-        None
+        observer.on_expand(&current);
+        self.visited.insert(current.clone());
+        let (neighbors, duplicates): (Vec<Board>, Vec<Board>) = BoardManager::neigbors_of(&current)
+            .into_iter()
+            .partition(|board| !self.visited.contains(board));
+        duplicates
+            .iter()
+            .for_each(|board| observer.on_duplicate(board));
+        neighbors.into_iter().for_each(|board| {
+            observer.on_generate(&board, &current);
+            self.parents.insert(board.clone(), current.clone());
+            self.stack.push_back(board);
+        });
+        Step::Expanded(current)
+    }
+
+    fn frontier(&self) -> Vec<&Board> {
+        self.stack.iter().rev().collect()
+    }
+
+    fn closed(&self) -> Vec<&[Tile]> {
+        self.visited.iter().map(BoardManager::tiles_of).collect()
+    }
+
+    fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
+
+impl Iterator for DfsStepper {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        (!self.finished).then(|| self.step(&mut NoopObserver))
     }
 }
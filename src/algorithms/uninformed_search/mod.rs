@@ -1,2 +1,3 @@
+pub mod dfs;
+pub mod optimal;
 pub mod ucs;
-pub mod dfs;
\ No newline at end of file
@@ -0,0 +1,239 @@
+use std::collections::{HashMap, LinkedList};
+
+use crate::{
+    algorithms::observer::{NoopObserver, SearchObserver},
+    board::{
+        board::{Board, BoardManager},
+        cell::Tile,
+        moves::Move,
+    },
+};
+
+/// Every shortest solution of a board, found by a breadth-first search that stops at the
+/// goal's layer.
+///
+/// The boards lying on a shortest path form a layered graph; counting the paths through it is
+/// cheap even when there are far too many to list, and listing them follows the moves in
+/// [`Move::ALL`] order, so the first solution is always the same.
+pub struct OptimalSolutions {
+    initial: Board,
+    length: usize,
+    /// Boards on a shortest path with their distance from the initial board and the number of
+    /// shortest paths from them to the goal.
+    on_path: HashMap<Vec<Tile>, (usize, u128)>,
+}
+
+impl OptimalSolutions {
+    /// Searches `initial_state`, returning `None` when the goal cannot be reached.
+    pub fn search(initial_state: &Board) -> Option<Self> {
+        Self::search_with(initial_state, &mut NoopObserver)
+    }
+
+    /// [`Self::search`], reporting the breadth-first search to `observer`. Returns `None` as
+    /// well once `observer` is cancelled.
+    pub fn search_with(initial_state: &Board, observer: &mut dyn SearchObserver) -> Option<Self> {
+        if !BoardManager::is_solvable(
+            BoardManager::size_of(initial_state),
+            BoardManager::tiles_of(initial_state),
+            BoardManager::goal_of(initial_state),
+        ) {
+            return None;
+        }
+
+        let mut layers: Vec<Vec<Board>> = vec![vec![initial_state.clone()]];
+        let mut distances: HashMap<Vec<Tile>, usize> =
+            HashMap::from([(BoardManager::tiles_of(initial_state).to_vec(), 0)]);
+        let mut goal = initial_state.match_goal().then(|| initial_state.clone());
+        while goal.is_none() {
+            let depth = layers.len();
+            let mut layer = Vec::new();
+            for board in layers.last().unwrap() {
+                if observer.is_cancelled() {
+                    return None;
+                }
+                observer.on_expand(board);
+                for neighbor in BoardManager::neigbors_of(board) {
+                    let tiles = BoardManager::tiles_of(&neighbor).to_vec();
+                    if distances.contains_key(&tiles) {
+                        observer.on_duplicate(&neighbor);
+                        continue;
+                    }
+                    observer.on_generate(&neighbor, board);
+                    distances.insert(tiles, depth);
+                    if neighbor.match_goal() {
+                        goal = Some(neighbor.clone());
+                    }
+                    layer.push(neighbor);
+                }
+            }
+            layers.push(layer);
+        }
+
+        // walk back from the goal one layer at a time, counting the paths to it
+        let goal = goal?;
+        let length = layers.len() - 1;
+        let mut on_path: HashMap<Vec<Tile>, (usize, u128)> =
+            HashMap::from([(BoardManager::tiles_of(&goal).to_vec(), (length, 1))]);
+        let mut frontier = vec![goal];
+        for depth in (0..length).rev() {
+            let mut previous = Vec::new();
+            for board in &frontier {
+                let count = on_path[BoardManager::tiles_of(board)].1;
+                for neighbor in BoardManager::neigbors_of(board) {
+                    let tiles = BoardManager::tiles_of(&neighbor);
+                    if distances.get(tiles) != Some(&depth) {
+                        continue;
+                    }
+                    let entry = on_path.entry(tiles.to_vec()).or_insert_with(|| {
+                        previous.push(neighbor.clone());
+                        (depth, 0)
+                    });
+                    entry.1 = entry.1.saturating_add(count);
+                }
+            }
+            frontier = previous;
+        }
+
+        Some(Self {
+            initial: initial_state.clone(),
+            length,
+            on_path,
+        })
+    }
+
+    /// Number of moves of every optimal solution.
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Number of distinct optimal solutions, saturating at `u128::MAX`.
+    pub fn count(&self) -> u128 {
+        self.on_path[BoardManager::tiles_of(&self.initial)].1
+    }
+
+    /// The first `k` optimal solutions, from the initial board to the goal.
+    pub fn first(&self, k: usize) -> Vec<LinkedList<Board>> {
+        let mut solutions = Vec::new();
+        if k == 0 {
+            return solutions;
+        }
+        let mut path = vec![self.initial.clone()];
+        self.collect(&mut path, k, &mut solutions);
+        solutions
+    }
+
+    pub fn all(&self) -> Vec<LinkedList<Board>> {
+        self.first(usize::MAX)
+    }
+
+    fn collect(&self, path: &mut Vec<Board>, k: usize, solutions: &mut Vec<LinkedList<Board>>) {
+        if path.len() == self.length + 1 {
+            solutions.push(path.iter().cloned().collect());
+            return;
+        }
+        let current = path.last().unwrap();
+        let next: Vec<Board> = Move::ALL
+            .iter()
+            .filter_map(|step| BoardManager::apply_move(current, *step))
+            .filter(|board| {
+                self.on_path
+                    .get(BoardManager::tiles_of(board))
+                    .is_some_and(|(depth, _)| *depth == path.len())
+            })
+            .collect();
+        for board in next {
+            if solutions.len() >= k {
+                return;
+            }
+            path.push(board);
+            self.collect(path, k, solutions);
+            path.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use crate::{
+        algorithms::{
+            cancellation::CancelAfter,
+            informed_search::heuristic::mahattan_distance::MahattanDistance,
+        },
+        board::{board::BoardManager, parser::BoardParser, scrambled},
+        GoalLayout, IdaStarMode, NoopObserver, ParallelIdaStarAlgorithms, Scramble, SearchStats,
+    };
+
+    use super::OptimalSolutions;
+
+    #[test]
+    fn it_should_count_and_list_every_optimal_solution() {
+        for (distance, seed) in [(12, 5), (16, 3), (20, 2)] {
+            let board = scrambled(3, GoalLayout::RowMajor, Scramble::Distance(distance), seed);
+            let solutions = OptimalSolutions::search(&board).unwrap();
+            let all = solutions.all();
+            assert_eq!(solutions.length(), distance);
+            assert_eq!(all.len() as u128, solutions.count());
+            assert!(all.iter().all(|path| path.len() == distance + 1
+                && path.back().unwrap().match_goal()
+                && path
+                    .iter()
+                    .zip(path.iter().skip(1))
+                    .all(|(from, to)| BoardManager::move_of(from, to).is_some())));
+            let distinct: HashSet<Vec<Vec<_>>> = all
+                .iter()
+                .map(|path| {
+                    path.iter()
+                        .map(|board| BoardManager::tiles_of(board).to_vec())
+                        .collect()
+                })
+                .collect();
+            assert_eq!(distinct.len(), all.len());
+
+            let ida = ParallelIdaStarAlgorithms::with(2, || Box::new(MahattanDistance::new()))
+                .mode(IdaStarMode::AllOptimal)
+                .solve_all(board, &mut NoopObserver);
+            assert_eq!(ida.len(), all.len());
+        }
+    }
+
+    #[test]
+    fn it_should_take_the_first_solutions_in_move_order() {
+        let board = scrambled(3, GoalLayout::RowMajor, Scramble::Distance(20), 2);
+        let solutions = OptimalSolutions::search(&board).unwrap();
+        assert!(solutions.count() > 2);
+        let first = solutions.first(2);
+        assert_eq!(first.len(), 2);
+        assert_eq!(first, solutions.all()[..2]);
+    }
+
+    #[test]
+    fn it_should_handle_solved_boards() {
+        let board = BoardParser::parse("1 2 3 4 5 6 7 8 0", false).unwrap();
+        let solutions = OptimalSolutions::search(&board).unwrap();
+        assert_eq!(solutions.length(), 0);
+        assert_eq!(solutions.count(), 1);
+        assert_eq!(solutions.all(), vec![[board].into_iter().collect()]);
+    }
+
+    #[test]
+    fn it_should_take_no_solutions_when_asked_for_none() {
+        let solved = BoardParser::parse("1 2 3 4 5 6 7 8 0", false).unwrap();
+        let scrambled = scrambled(3, GoalLayout::RowMajor, Scramble::Distance(8), 1);
+        for board in [solved, scrambled] {
+            let solutions = OptimalSolutions::search(&board).unwrap();
+            assert!(solutions.first(0).is_empty());
+        }
+    }
+
+    #[test]
+    fn it_should_stop_when_cancelled_mid_search() {
+        let board = scrambled(4, GoalLayout::RowMajor, Scramble::Uniform, 1);
+        let mut observer = (SearchStats::new(), CancelAfter(100));
+        let path = OptimalSolutions::search_with(&board, &mut observer);
+        assert!(path.is_none());
+        assert_eq!(observer.0.expanded, 100);
+        assert!(observer.0.generated > 0);
+    }
+}
@@ -1,46 +1,112 @@
 use std::collections::{BTreeMap, HashSet, LinkedList, VecDeque};
 
-use crate::{board::board::BoardManager, Algorithms, Board};
+use crate::{
+    algorithms::{
+        observer::{NoopObserver, SearchObserver},
+        stepper::{SearchStepper, Step},
+    },
+    board::{board::BoardManager, cell::Tile},
+    Algorithms, Board,
+};
 
-pub struct UcsAlgorithms {
+pub struct UcsAlgorithms;
+
+impl UcsAlgorithms {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn stepper(&self, initial_state: Board) -> UcsStepper {
+        UcsStepper::new(initial_state)
+    }
+}
+
+impl Default for UcsAlgorithms {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Algorithms for UcsAlgorithms {
+    fn solve_with(
+        &mut self,
+        initial_state: Board,
+        observer: &mut dyn SearchObserver,
+    ) -> Option<LinkedList<Board>> {
+        self.stepper(initial_state).run(observer)
+    }
+}
+
+pub struct UcsStepper {
+    queue: VecDeque<Board>,
     visisted: HashSet<Board>,
     parents: BTreeMap<Board, Board>,
+    finished: bool,
 }
 
-impl UcsAlgorithms {
-    pub fn new() -> Self {
+impl UcsStepper {
+    pub fn new(initial_state: Board) -> Self {
         Self {
+            queue: VecDeque::from([initial_state]),
             visisted: HashSet::new(),
             parents: BTreeMap::new(),
+            finished: false,
         }
     }
 }
 
-impl Algorithms for UcsAlgorithms {
-    fn solve(&mut self, initial_state: Board) -> Option<std::collections::LinkedList<Board>> {
-        let mut solutions: LinkedList<Board> = LinkedList::new();
-        let mut queue = VecDeque::new();
-        queue.push_back(initial_state);
-        while let Some(current) = queue.pop_front() {
-            if current.match_goal() {
-                let mut current = current;
-                solutions.push_front(current.clone());
-                while let Some(parent) = self.parents.get(&current) {
-                    solutions.push_front(parent.clone());
-                    current = parent.clone();
-                }
-                return Some(solutions);
+impl SearchStepper for UcsStepper {
+    fn step(&mut self, observer: &mut dyn SearchObserver) -> Step {
+        let Some(current) = self.queue.pop_front() else {
+            self.finished = true;
+            return Step::Exhausted;
+        };
+        if current.match_goal() {
+            let mut solutions: LinkedList<Board> = LinkedList::new();
+            let mut current = current;
+            solutions.push_front(current.clone());
+            while let Some(parent) = self.parents.get(&current) {
+                solutions.push_front(parent.clone());
+                current = parent.clone();
             }
-            self.visisted.insert(current.clone());
-            let neighbors_idx = BoardManager::neigbors_of(&current);
-            neighbors_idx
-                .into_iter()
-                .filter(|board| !self.visisted.contains(board))
-                .for_each(|board| {
-                    self.parents.insert(board.clone(), current.clone());
-                    queue.push_back(board);
-                });
+            observer.on_solution(&solutions);
+            self.finished = true;
+            return Step::Solved(solutions);
         }
-        None
+        observer.on_expand(&current);
+        self.visisted.insert(current.clone());
+        let neighbors_idx = BoardManager::neigbors_of(&current);
+        let (neighbors, duplicates): (Vec<Board>, Vec<Board>) = neighbors_idx
+            .into_iter()
+            .partition(|board| !self.visisted.contains(board));
+        duplicates
+            .iter()
+            .for_each(|board| observer.on_duplicate(board));
+        neighbors.into_iter().for_each(|board| {
+            observer.on_generate(&board, &current);
+            self.parents.insert(board.clone(), current.clone());
+            self.queue.push_back(board);
+        });
+        Step::Expanded(current)
+    }
+
+    fn frontier(&self) -> Vec<&Board> {
+        self.queue.iter().collect()
+    }
+
+    fn closed(&self) -> Vec<&[Tile]> {
+        self.visisted.iter().map(BoardManager::tiles_of).collect()
+    }
+
+    fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
+
+impl Iterator for UcsStepper {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        (!self.finished).then(|| self.step(&mut NoopObserver))
     }
 }
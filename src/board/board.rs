@@ -1,12 +1,24 @@
 use std::{cmp::Ordering, fmt::Display, hash::Hash};
 
-use super::{board_config::BoardConfig, cell::Tile};
-use rand::{thread_rng, Rng};
+use crate::error::{PermutationError, PuzzleError};
+
+use super::{board_config::BoardConfig, cell::Tile, moves::Move, scramble::Scramble};
+use rand::{thread_rng, Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 
 #[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        try_from = "super::serialization::BoardRepr",
+        into = "super::serialization::BoardRepr"
+    )
+)]
 pub struct Board {
     tiles: Vec<Tile>,
     config: BoardConfig,
+    seed: Option<u64>,
 }
 
 impl Hash for Board {
@@ -30,35 +42,52 @@ impl PartialOrd for Board {
 }
 
 impl Ord for Board {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        match &self.config {
-            BoardConfig::Uninformed(_) => self.tiles.cmp(&other.tiles),
-            BoardConfig::Informed(config) => match &other.config {
-                BoardConfig::Uninformed(_) => self.tiles.cmp(&other.tiles),
-                BoardConfig::Informed(other_config) => {
-                    if config.weight + config.depth < other_config.weight + other_config.depth {
-                        Ordering::Greater
-                    } else if config.weight + config.depth
-                        == other_config.weight + other_config.depth
-                    {
-                        Ordering::Equal
-                    } else {
-                        Ordering::Less
-                    }
-                }
-            },
-        }
+    // boards are identified by their tiles alone, like `Eq` and `Hash` do; searches order them
+    // by priority through `SearchNode` instead
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.tiles.cmp(&other.tiles)
     }
 }
 
 impl Board {
-    fn from(mut config: BoardConfig, temperature: f32) -> Self {
-        let (tiles, empty_tile_idx) =
-            BoardManager::generate_random_board(config.n(), &config.goal_state(), temperature);
-
+    fn from(
+        mut config: BoardConfig,
+        tiles: Vec<Tile>,
+        empty_tile_idx: u8,
+        seed: Option<u64>,
+    ) -> Self {
         config.set_empty_tile_idx(empty_tile_idx);
 
-        Self { tiles, config }
+        Self {
+            tiles,
+            config,
+            seed,
+        }
+    }
+
+    pub(crate) fn with_tiles(mut config: BoardConfig, tiles: Vec<Tile>) -> Self {
+        let empty_tile_idx = tiles
+            .iter()
+            .position(|tile| *tile == Tile::Empty)
+            .expect("Board: tiles must contain the empty tile");
+
+        config.set_empty_tile_idx(empty_tile_idx as u8);
+
+        Self {
+            tiles,
+            config,
+            seed: None,
+        }
+    }
+
+    /// Seed the board was scrambled with, `None` for parsed boards and injected generators.
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn set_seed(&mut self, seed: Option<u64>) {
+        self.seed = seed;
     }
 
     pub fn match_goal(&self) -> bool {
@@ -83,12 +112,13 @@ impl Display for Board {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Temperature(pub f32);
 
 impl Temperature {
-    pub fn from(temperature: f32) -> Result<Self, &'static str> {
+    pub fn from(temperature: f32) -> Result<Self, PuzzleError> {
         if temperature <= 0.0 || temperature > 1.0 {
-            Err("Temperature: temperature must be within 0.0 and 1.0 (inclusive)")
+            Err(PuzzleError::InvalidTemperature(temperature))
         } else {
             Ok(Self(temperature))
         }
@@ -98,6 +128,9 @@ impl Temperature {
 pub struct BoardBuilder {
     config: Option<BoardConfig>,
     temperature: Option<Temperature>,
+    scramble: Option<Scramble>,
+    seed: Option<u64>,
+    rng: Option<Box<dyn RngCore>>,
 }
 
 impl BoardBuilder {
@@ -105,6 +138,9 @@ impl BoardBuilder {
         Self {
             config: None,
             temperature: None,
+            scramble: None,
+            seed: None,
+            rng: None,
         }
     }
 
@@ -118,14 +154,58 @@ impl BoardBuilder {
         self
     }
 
-    pub fn build(mut self) -> Result<Board, &'static str> {
+    /// Replaces the temperature-driven random walk with one of the [`Scramble`] generators.
+    pub fn scramble(mut self, scramble: Scramble) -> Self {
+        self.scramble = Some(scramble);
+        self
+    }
+
+    /// Scrambles with a ChaCha8 generator seeded by `seed`, so the same seed always yields the
+    /// same board on every platform.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Scrambles with the given generator instead; the resulting board carries no seed.
+    pub fn rng(mut self, rng: Box<dyn RngCore>) -> Self {
+        self.rng = Some(rng);
+        self
+    }
+
+    pub fn build(mut self) -> Result<Board, PuzzleError> {
         if self.config.is_none() {
-            return Err("BoardBuilder: config must be provided");
+            return Err(PuzzleError::MissingField("config"));
         }
-        Ok(Board::from(
-            self.config.take().unwrap(),
-            self.temperature.take().unwrap_or(Temperature(0.3)).0,
-        ))
+        if self.seed.is_some() && self.rng.is_some() {
+            return Err(PuzzleError::ConflictingFields("seed", "rng"));
+        }
+
+        let (mut rng, seed) = match self.rng.take() {
+            Some(rng) => (rng, None),
+            None => {
+                // without an explicit seed one is drawn, so every board can be reproduced
+                let seed = self.seed.take().unwrap_or_else(|| thread_rng().gen());
+                (
+                    Box::new(ChaCha8Rng::seed_from_u64(seed)) as Box<dyn RngCore>,
+                    Some(seed),
+                )
+            }
+        };
+
+        let config = self.config.take().unwrap();
+
+        let (tiles, empty_tile_idx) = match self.scramble.take() {
+            Some(scramble) => scramble.generate(config.n(), config.goal_state(), rng.as_mut())?,
+            None => BoardManager::generate_random_board(
+                config.n(),
+                config.goal_state(),
+                self.temperature.take().unwrap_or(Temperature(0.3)).0,
+                rng.as_mut(),
+            ),
+        };
+
+        Ok(Board::from(config, tiles, empty_tile_idx, seed))
     }
 }
 
@@ -136,10 +216,30 @@ impl BoardManager {
         &board.tiles
     }
 
+    pub fn config_of(board: &Board) -> &BoardConfig {
+        &board.config
+    }
+
+    /// The move that turns `from` into its neighbour `to`.
+    pub fn move_of(from: &Board, to: &Board) -> Option<Move> {
+        Move::between(
+            from.config.empty_tile_idx() as usize,
+            to.config.empty_tile_idx() as usize,
+            from.config.n() as usize,
+        )
+    }
+
     pub fn goal_of(board: &Board) -> &[Tile] {
         board.config.goal_state()
     }
 
+    /// Copy of `board` that has to reach `goal_state` instead of its own goal.
+    pub fn retarget(board: &Board, goal_state: &[Tile]) -> Board {
+        let mut board = board.clone();
+        board.config.set_goal_state(goal_state.to_vec());
+        board
+    }
+
     pub fn size_of(board: &Board) -> u8 {
         board.config.n()
     }
@@ -155,6 +255,10 @@ impl BoardManager {
         board
     }
 
+    pub fn is_informed(board: &Board) -> bool {
+        matches!(board.config, BoardConfig::Informed(_))
+    }
+
     pub fn total_weight(board: &Board) -> usize {
         if board.config.get_depth().is_none() || board.config.get_weight().is_none() {
             0
@@ -171,10 +275,99 @@ impl BoardManager {
         }
     }
 
-    pub fn neigbors_of(board: &Board) -> Vec<Board> {
+    /// Checks that `values` holds every number of 0..n² exactly once.
+    pub(crate) fn check_permutation(n: usize, values: &[usize]) -> Result<(), PermutationError> {
+        Self::check_cells(n * n, values)
+    }
+
+    /// Checks that `values` holds every number of 0..cells exactly once, for boards that are
+    /// not square.
+    pub(crate) fn check_cells(cells: usize, values: &[usize]) -> Result<(), PermutationError> {
+        if values.len() != cells {
+            return Err(PermutationError::LengthMismatch {
+                expected: cells,
+                found: values.len(),
+            });
+        }
+        if !values.contains(&0) {
+            return Err(PermutationError::MissingBlank);
+        }
+
+        let mut seen = vec![false; cells];
+        for &value in values {
+            if value >= cells {
+                return Err(PermutationError::TileOutOfRange {
+                    value,
+                    max: cells - 1,
+                });
+            }
+            if seen[value] {
+                return Err(PermutationError::DuplicateTile(value as u8));
+            }
+            seen[value] = true;
+        }
+
+        Ok(())
+    }
+
+    /// A board can reach the goal iff the parity of the permutation mapping it onto the goal
+    /// equals the parity of the distance the empty tile has to travel, since every move is a
+    /// transposition that also moves the empty tile by one cell.
+    pub fn is_solvable(n: u8, tiles: &[Tile], goal_state: &[Tile]) -> bool {
+        let n = n as usize;
+        if tiles.len() != n * n || goal_state.len() != n * n {
+            return false;
+        }
+
+        let mut goal_idx = vec![usize::MAX; n * n];
+        for (idx, tile) in goal_state.iter().enumerate() {
+            let value = tile.get_value() as usize;
+            if value >= n * n || goal_idx[value] != usize::MAX {
+                return false;
+            }
+            goal_idx[value] = idx;
+        }
+
+        let mut permutation = Vec::with_capacity(n * n);
+        for tile in tiles {
+            let value = tile.get_value() as usize;
+            if value >= n * n || goal_idx[value] == usize::MAX {
+                return false;
+            }
+            permutation.push(goal_idx[value]);
+        }
+
+        let mut visited = vec![false; n * n];
+        let mut transpositions = 0;
+        for start in 0..permutation.len() {
+            if visited[start] {
+                continue;
+            }
+            let mut idx = start;
+            let mut length = 0;
+            while !visited[idx] {
+                visited[idx] = true;
+                idx = permutation[idx];
+                length += 1;
+            }
+            transpositions += length - 1;
+        }
+
+        let empty_idx = tiles.iter().position(|tile| *tile == Tile::Empty);
+        let goal_empty_idx = goal_idx[0];
+        match empty_idx {
+            None => false,
+            Some(empty_idx) => {
+                let distance = (empty_idx % n).abs_diff(goal_empty_idx % n)
+                    + (empty_idx / n).abs_diff(goal_empty_idx / n);
+                transpositions % 2 == distance % 2
+            }
+        }
+    }
+
+    /// Cells the empty tile at `idx` can move to, in left, right, up, down order.
+    pub(crate) fn adjacent_of(idx: usize, n: usize) -> Vec<usize> {
         let mut neighbors = Vec::<usize>::with_capacity(4);
-        let idx = board.config.empty_tile_idx() as usize;
-        let n = board.config.n() as usize;
         if let Some(left) = Self::move_left(idx, n) {
             neighbors.push(left)
         }
@@ -189,6 +382,12 @@ impl BoardManager {
         }
         neighbors.shrink_to_fit();
         neighbors
+    }
+
+    pub fn neigbors_of(board: &Board) -> Vec<Board> {
+        let idx = board.config.empty_tile_idx() as usize;
+        let n = board.config.n() as usize;
+        Self::adjacent_of(idx, n)
             .into_iter()
             .map(|idx| {
                 let mut neighbor = Self::swap_empty_tile_with(idx, board);
@@ -200,22 +399,47 @@ impl BoardManager {
             .collect()
     }
 
+    /// The neighbour reached by moving the empty tile in direction `step`, if it stays on the
+    /// board.
+    pub fn apply_move(board: &Board, step: Move) -> Option<Board> {
+        let idx = step.apply(
+            board.config.empty_tile_idx() as usize,
+            board.config.n() as usize,
+        )?;
+        let mut neighbor = Self::swap_empty_tile_with(idx, board);
+        if let Some(depth) = neighbor.config.get_mut_depth() {
+            *depth += 1;
+        }
+        Some(neighbor)
+    }
+
     fn swap_empty_tile_with(idx: usize, board: &Board) -> Board {
         let mut tiles = board.tiles.to_vec();
         tiles.swap(board.config.empty_tile_idx() as usize, idx);
         let mut config = board.config.clone();
         config.set_empty_tile_idx(idx as u8);
-        Board { tiles, config }
+        Board {
+            tiles,
+            config,
+            seed: board.seed,
+        }
     }
 
-    fn generate_random_board(n: u8, goal_state: &[Tile], temperature: f32) -> (Vec<Tile>, u8) {
+    fn generate_random_board(
+        n: u8,
+        goal_state: &[Tile],
+        temperature: f32,
+        rng: &mut dyn RngCore,
+    ) -> (Vec<Tile>, u8) {
         let mut tiles: Vec<Tile> = goal_state.to_vec();
 
         let mut times = (temperature * 1000.0) as u16;
 
-        let mut idx = tiles.len() - 1;
-
-        let mut rng = thread_rng();
+        // the walk has to start wherever the goal keeps its empty tile
+        let mut idx = goal_state
+            .iter()
+            .position(|tile| *tile == Tile::Empty)
+            .expect("BoardManager: goal state must contain the empty tile");
 
         let map_to_function = |number: u8| {
             if number == 0 {
@@ -247,7 +471,7 @@ impl BoardManager {
     }
 
     fn move_left(idx: usize, n: usize) -> Option<usize> {
-        if idx % n == 0 {
+        if idx.is_multiple_of(n) {
             return None;
         }
         Some(idx - 1)
@@ -275,6 +499,139 @@ impl BoardManager {
     }
 }
 
+#[cfg(test)]
+mod test {
+    use std::cmp::Ordering;
+
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::{
+        board::{
+            board::BoardManager, goal_layout::GoalLayout, parser::BoardParser, BoardConfigBuilder,
+        },
+        error::{PermutationError, PuzzleError},
+    };
+
+    use super::{BoardBuilder, Temperature};
+
+    fn builder() -> BoardBuilder {
+        let config = BoardConfigBuilder::builder()
+            .essential(3, vec![1, 2, 3, 4, 5, 6, 7, 8, 0])
+            .build()
+            .unwrap();
+        BoardBuilder::builder()
+            .config(config)
+            .temperature(Temperature(0.1))
+    }
+
+    #[test]
+    fn it_should_reproduce_a_board_from_its_seed() {
+        let board = builder().seed(42).build().unwrap();
+        assert_eq!(board.seed(), Some(42));
+
+        let again = builder().seed(42).build().unwrap();
+        assert_eq!(
+            BoardManager::tiles_of(&board),
+            BoardManager::tiles_of(&again)
+        );
+
+        let random = builder().build().unwrap();
+        let replayed = builder().seed(random.seed().unwrap()).build().unwrap();
+        assert_eq!(
+            BoardManager::tiles_of(&random),
+            BoardManager::tiles_of(&replayed)
+        );
+    }
+
+    #[test]
+    fn it_should_generate_solvable_permutations_of_any_goal() {
+        for layout in [
+            GoalLayout::RowMajor,
+            GoalLayout::BlankFirst,
+            GoalLayout::Snail,
+        ] {
+            for n in 2..=5 {
+                for seed in 0..10 {
+                    let config = BoardConfigBuilder::builder()
+                        .layout(n, layout)
+                        .build()
+                        .unwrap();
+                    let board = BoardBuilder::builder()
+                        .config(config)
+                        .temperature(Temperature(0.05))
+                        .seed(seed)
+                        .build()
+                        .unwrap();
+
+                    let tiles = BoardManager::tiles_of(&board);
+                    let goal_state = BoardManager::goal_of(&board);
+                    let mut values: Vec<u8> = tiles.iter().map(|tile| tile.get_value()).collect();
+                    values.sort();
+                    assert_eq!(values, (0..n * n).collect::<Vec<u8>>());
+                    assert_eq!(
+                        tiles[BoardManager::empty_tile_idx(&board) as usize].get_value(),
+                        0
+                    );
+                    assert!(BoardManager::is_solvable(n, tiles, goal_state));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_report_missing_and_invalid_fields() {
+        assert_eq!(
+            BoardBuilder::builder().build().unwrap_err(),
+            PuzzleError::MissingField("config")
+        );
+        assert_eq!(
+            BoardConfigBuilder::builder().build().unwrap_err(),
+            PuzzleError::MissingField("essential")
+        );
+        assert_eq!(
+            BoardConfigBuilder::builder()
+                .essential(2, vec![1, 2, 2, 0])
+                .build()
+                .unwrap_err(),
+            PermutationError::DuplicateTile(2).into()
+        );
+        assert_eq!(
+            Temperature::from(1.5).unwrap_err(),
+            PuzzleError::InvalidTemperature(1.5)
+        );
+    }
+
+    #[test]
+    fn it_should_scramble_with_an_injected_rng() {
+        let board = builder()
+            .rng(Box::new(ChaCha8Rng::seed_from_u64(7)))
+            .build()
+            .unwrap();
+        assert_eq!(board.seed(), None);
+        assert!(builder()
+            .seed(7)
+            .rng(Box::new(ChaCha8Rng::seed_from_u64(7)))
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn it_should_order_boards_by_their_tiles() {
+        let board = BoardParser::parse("1 2 3 4 5 6 7 0 8", true).unwrap();
+        let neighbors = BoardManager::neigbors_of(&BoardManager::assign_weight(board.clone(), 1));
+        let [first, second] = [&neighbors[0], &neighbors[1]]
+            .map(|neighbor| BoardManager::assign_weight(neighbor.clone(), 2));
+        // equal costs no longer make different boards equal
+        assert_eq!(
+            BoardManager::total_weight(&first),
+            BoardManager::total_weight(&second)
+        );
+        assert_ne!(first.cmp(&second), Ordering::Equal);
+        assert_eq!(board.cmp(&board.clone()), Ordering::Equal);
+    }
+}
+
 // #[cfg(test)]
 // mod test {
 //     use crate::board::{board::BoardManager, cell::Tile};
@@ -1,4 +1,6 @@
-use super::cell::Tile;
+use crate::error::PuzzleError;
+
+use super::{board::BoardManager, cell::Tile, goal_layout::GoalLayout};
 
 #[derive(Debug, PartialEq, PartialOrd, Eq, Ord, Hash, Clone)]
 pub struct UninformedConfig {
@@ -17,6 +19,14 @@ pub struct InformedConfig {
 }
 
 #[derive(Debug, PartialEq, PartialOrd, Eq, Ord, Hash, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        try_from = "super::serialization::BoardConfigRepr",
+        into = "super::serialization::BoardConfigRepr"
+    )
+)]
 pub enum BoardConfig {
     Uninformed(UninformedConfig),
     Informed(InformedConfig),
@@ -37,6 +47,13 @@ impl BoardConfig {
         }
     }
 
+    pub fn set_goal_state(&mut self, goal_state: Vec<Tile>) {
+        match self {
+            Self::Uninformed(config) => config.goal_state = goal_state,
+            Self::Informed(config) => config.goal_state = goal_state,
+        }
+    }
+
     pub fn empty_tile_idx(&self) -> u8 {
         match self {
             Self::Uninformed(config) => config.empty_tile_idx,
@@ -100,17 +117,16 @@ impl BoardConfigBuilder {
     }
 
     pub fn essential(mut self, n: u8, goal_state: Vec<u8>) -> Self {
-        self.goal_state = Some(
-            goal_state
-                .into_iter()
-                .map(|value| Tile::with_value(value))
-                .collect(),
-        );
+        self.goal_state = Some(goal_state.into_iter().map(Tile::with_value).collect());
         self.n = Some(n);
 
         self
     }
 
+    pub fn layout(self, n: u8, layout: GoalLayout) -> Self {
+        self.essential(n, layout.goal_state(n))
+    }
+
     pub fn with_depth(mut self, depth: usize) -> Self {
         self.depth = Some(depth);
         self
@@ -126,14 +142,23 @@ impl BoardConfigBuilder {
         self
     }
 
-    pub fn build(mut self) -> Result<BoardConfig, &'static str> {
+    pub fn build(mut self) -> Result<BoardConfig, PuzzleError> {
         if self.n.is_none() || self.goal_state.is_none() {
-            return Err("BoardConfigBuilder: must call self.essential()");
+            return Err(PuzzleError::MissingField("essential"));
         }
 
         let n = self.n.take().unwrap();
         let goal_state = self.goal_state.take().unwrap();
 
+        if !(2..=16).contains(&n) {
+            return Err(PuzzleError::InvalidSize(n as usize));
+        }
+        let values: Vec<usize> = goal_state
+            .iter()
+            .map(|tile| tile.get_value() as usize)
+            .collect();
+        BoardManager::check_permutation(n as usize, &values)?;
+
         if self.informed.is_none() {
             Ok(BoardConfig::Uninformed(UninformedConfig {
                 n,
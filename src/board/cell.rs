@@ -1,4 +1,9 @@
 #[derive(PartialEq, Eq, Hash, Debug, Clone, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "u8", into = "u8")
+)]
 pub enum Tile {
     Empty,
     Value(u8), // only supports solving n-puzzle where n is less than 7
@@ -24,6 +29,18 @@ impl Tile {
     }
 }
 
+impl From<u8> for Tile {
+    fn from(value: u8) -> Self {
+        Self::with_value(value)
+    }
+}
+
+impl From<Tile> for u8 {
+    fn from(tile: Tile) -> Self {
+        tile.get_value()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::Tile;
@@ -0,0 +1,120 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::error::PuzzleError;
+
+/// Well-known arrangements of the goal state, generated for any board size.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum GoalLayout {
+    /// `1 2 3 / 4 5 6 / 7 8 0`
+    #[default]
+    RowMajor,
+    /// `0 1 2 / 3 4 5 / 6 7 8`
+    BlankFirst,
+    /// `1 2 3 / 8 0 4 / 7 6 5`, tiles follow a clockwise spiral and the blank ends it
+    Snail,
+}
+
+impl GoalLayout {
+    pub fn goal_state(&self, n: u8) -> Vec<u8> {
+        let size = n as usize * n as usize;
+        match self {
+            Self::RowMajor => {
+                let mut goal_state: Vec<u8> = (1..size).map(|value| value as u8).collect();
+                goal_state.push(0);
+                goal_state
+            }
+            Self::BlankFirst => (0..size).map(|value| value as u8).collect(),
+            Self::Snail => {
+                let n = n as isize;
+                let mut goal_state = vec![0; size];
+                let (mut row, mut col) = (0, 0);
+                let (mut d_row, mut d_col) = (0, 1);
+                let (mut top, mut bottom, mut left, mut right) = (0, n - 1, 0, n - 1);
+                for value in 1..size {
+                    goal_state[(row * n + col) as usize] = value as u8;
+                    // turn clockwise when the next cell leaves the unfilled ring
+                    let (next_row, next_col) = (row + d_row, col + d_col);
+                    if next_col > right {
+                        (d_row, d_col) = (1, 0);
+                        top += 1;
+                    } else if next_row > bottom {
+                        (d_row, d_col) = (0, -1);
+                        right -= 1;
+                    } else if next_col < left {
+                        (d_row, d_col) = (-1, 0);
+                        bottom -= 1;
+                    } else if next_row < top {
+                        (d_row, d_col) = (0, 1);
+                        left += 1;
+                    }
+                    row += d_row;
+                    col += d_col;
+                }
+                goal_state
+            }
+        }
+    }
+}
+
+impl FromStr for GoalLayout {
+    type Err = PuzzleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "row-major" | "row_major" | "rowmajor" => Ok(Self::RowMajor),
+            "blank-first" | "blank_first" | "blankfirst" => Ok(Self::BlankFirst),
+            "snail" | "spiral" => Ok(Self::Snail),
+            _ => Err(PuzzleError::UnknownLayout(s.to_string())),
+        }
+    }
+}
+
+impl Display for GoalLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RowMajor => write!(f, "row-major"),
+            Self::BlankFirst => write!(f, "blank-first"),
+            Self::Snail => write!(f, "snail"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::GoalLayout;
+
+    #[test]
+    fn it_should_generate_row_major_and_blank_first_goals() {
+        assert_eq!(
+            GoalLayout::RowMajor.goal_state(3),
+            vec![1, 2, 3, 4, 5, 6, 7, 8, 0]
+        );
+        assert_eq!(
+            GoalLayout::BlankFirst.goal_state(3),
+            vec![0, 1, 2, 3, 4, 5, 6, 7, 8]
+        );
+    }
+
+    #[test]
+    fn it_should_generate_snail_goals() {
+        assert_eq!(GoalLayout::Snail.goal_state(2), vec![1, 2, 0, 3]);
+        assert_eq!(
+            GoalLayout::Snail.goal_state(3),
+            vec![1, 2, 3, 8, 0, 4, 7, 6, 5]
+        );
+        assert_eq!(
+            GoalLayout::Snail.goal_state(4),
+            vec![1, 2, 3, 4, 12, 13, 14, 5, 11, 0, 15, 6, 10, 9, 8, 7]
+        );
+        let mut goal_state = GoalLayout::Snail.goal_state(7);
+        goal_state.sort();
+        assert_eq!(goal_state, (0..49).collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn it_should_parse_layout_names() {
+        assert_eq!("snail".parse(), Ok(GoalLayout::Snail));
+        assert_eq!("Blank-First".parse(), Ok(GoalLayout::BlankFirst));
+        assert!("diagonal".parse::<GoalLayout>().is_err());
+    }
+}
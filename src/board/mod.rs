@@ -1,5 +1,34 @@
+#[allow(clippy::module_inception)]
 pub mod board;
 mod board_config;
 pub mod cell;
+pub mod goal_layout;
+pub mod moves;
+pub mod parser;
+pub mod scramble;
+#[cfg(feature = "serde")]
+mod serialization;
 
 pub use board_config::{BoardConfig, BoardConfigBuilder};
+
+/// Informed board of size `n`, `scramble`d away from its `layout` goal with `seed`, as the
+/// solver tests build their instances.
+#[cfg(test)]
+pub(crate) fn scrambled(
+    n: u8,
+    layout: goal_layout::GoalLayout,
+    scramble: scramble::Scramble,
+    seed: u64,
+) -> board::Board {
+    let config = BoardConfigBuilder::builder()
+        .layout(n, layout)
+        .with_informed()
+        .build()
+        .unwrap();
+    board::BoardBuilder::builder()
+        .config(config)
+        .scramble(scramble)
+        .seed(seed)
+        .build()
+        .unwrap()
+}
@@ -0,0 +1,91 @@
+use std::fmt::Display;
+
+/// Direction the empty tile travels in a single move.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Move {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Move {
+    pub const ALL: [Move; 4] = [Move::Up, Move::Down, Move::Left, Move::Right];
+
+    /// The move that takes the empty tile from `from` to the adjacent cell `to`.
+    pub fn between(from: usize, to: usize, n: usize) -> Option<Move> {
+        if to + n == from {
+            Some(Move::Up)
+        } else if from + n == to {
+            Some(Move::Down)
+        } else if to + 1 == from && from / n == to / n {
+            Some(Move::Left)
+        } else if from + 1 == to && from / n == to / n {
+            Some(Move::Right)
+        } else {
+            None
+        }
+    }
+
+    /// Cell the empty tile reaches from `idx`, if it stays on the board.
+    pub fn apply(&self, idx: usize, n: usize) -> Option<usize> {
+        match self {
+            Move::Up if idx >= n => Some(idx - n),
+            Move::Down if idx + n < n * n => Some(idx + n),
+            Move::Left if !idx.is_multiple_of(n) => Some(idx - 1),
+            Move::Right if idx % n != n - 1 => Some(idx + 1),
+            _ => None,
+        }
+    }
+
+    pub fn inverse(&self) -> Move {
+        match self {
+            Move::Up => Move::Down,
+            Move::Down => Move::Up,
+            Move::Left => Move::Right,
+            Move::Right => Move::Left,
+        }
+    }
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Move::Up => write!(f, "U"),
+            Move::Down => write!(f, "D"),
+            Move::Left => write!(f, "L"),
+            Move::Right => write!(f, "R"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Move;
+
+    #[test]
+    fn it_should_find_the_move_between_adjacent_cells() {
+        assert_eq!(Move::between(4, 1, 3), Some(Move::Up));
+        assert_eq!(Move::between(4, 7, 3), Some(Move::Down));
+        assert_eq!(Move::between(4, 3, 3), Some(Move::Left));
+        assert_eq!(Move::between(4, 5, 3), Some(Move::Right));
+        assert_eq!(Move::between(3, 2, 3), None);
+        assert_eq!(Move::between(4, 8, 3), None);
+    }
+
+    #[test]
+    fn it_should_stay_on_the_board() {
+        assert_eq!(Move::Up.apply(1, 3), None);
+        assert_eq!(Move::Left.apply(3, 3), None);
+        assert_eq!(Move::Right.apply(5, 3), None);
+        assert_eq!(Move::Down.apply(7, 3), None);
+        for step in Move::ALL {
+            assert_eq!(step.inverse().apply(step.apply(4, 3).unwrap(), 3), Some(4));
+        }
+    }
+}
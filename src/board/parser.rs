@@ -0,0 +1,235 @@
+use crate::error::{PermutationError, PuzzleError};
+
+use super::{
+    board::{Board, BoardManager},
+    cell::Tile,
+    goal_layout::GoalLayout,
+    BoardConfigBuilder,
+};
+
+/// Reads boards written either in the common n-puzzle file format
+///
+/// ```text
+/// # comments start with '#'
+/// 3
+/// 1 2 3
+/// 4 5 6
+/// 7 8 0
+/// ```
+///
+/// or on a single line such as `1 2 3 4 5 6 7 8 0`, where the size is inferred
+/// from the number of tiles.
+pub struct BoardParser;
+
+impl BoardParser {
+    pub const MIN_SIZE: usize = 2;
+    pub const MAX_SIZE: usize = 16;
+
+    /// Parses the board against the default goal (1..n² followed by the blank).
+    pub fn parse(input: &str, informed: bool) -> Result<Board, PuzzleError> {
+        Self::parse_with_layout(input, GoalLayout::RowMajor, informed)
+    }
+
+    pub fn parse_with_layout(
+        input: &str,
+        layout: GoalLayout,
+        informed: bool,
+    ) -> Result<Board, PuzzleError> {
+        let (n, tiles) = Self::parse_tiles(input)?;
+        Self::build(n, tiles, layout.goal_state(n), informed)
+    }
+
+    pub fn parse_with_goal(
+        input: &str,
+        goal_state: Vec<u8>,
+        informed: bool,
+    ) -> Result<Board, PuzzleError> {
+        let (n, tiles) = Self::parse_tiles(input)?;
+        Self::build(n, tiles, goal_state, informed)
+    }
+
+    /// Returns the size and the tiles of the board, checking that the tiles are
+    /// a permutation of 0..n² but not whether the board is solvable.
+    pub fn parse_tiles(input: &str) -> Result<(u8, Vec<u8>), PuzzleError> {
+        let mut rows = Vec::new();
+        for (idx, line) in input.lines().enumerate() {
+            let content = line.split('#').next().unwrap_or_default();
+            let row = content
+                .split_whitespace()
+                .map(|token| {
+                    token.parse::<usize>().map_err(|_| PuzzleError::Syntax {
+                        line: idx + 1,
+                        message: format!("'{}' is not a number", token),
+                    })
+                })
+                .collect::<Result<Vec<usize>, PuzzleError>>()?;
+            if !row.is_empty() {
+                rows.push((idx + 1, row));
+            }
+        }
+
+        if rows.is_empty() {
+            return Err(PuzzleError::Syntax {
+                line: 0,
+                message: "input does not contain any tiles".to_string(),
+            });
+        }
+
+        let (n, values) = if rows.len() == 1 {
+            let values = rows.pop().unwrap().1;
+            let n = (1..=Self::MAX_SIZE)
+                .find(|n| n * n == values.len())
+                .ok_or(PuzzleError::InvalidSize(values.len()))?;
+            (n, values)
+        } else {
+            let header = &rows[0].1;
+            if header.len() != 1 {
+                return Err(PuzzleError::Syntax {
+                    line: rows[0].0,
+                    message: format!("expected the board size but found {} numbers", header.len()),
+                });
+            }
+            let n = header[0];
+            if !(Self::MIN_SIZE..=Self::MAX_SIZE).contains(&n) {
+                return Err(PuzzleError::InvalidSize(n));
+            }
+            if rows.len() - 1 != n {
+                return Err(PuzzleError::Syntax {
+                    line: rows[rows.len() - 1].0,
+                    message: format!("expected {} rows but found {}", n, rows.len() - 1),
+                });
+            }
+            let mut values = Vec::with_capacity(n * n);
+            for (line, row) in rows.into_iter().skip(1) {
+                if row.len() != n {
+                    return Err(PuzzleError::Syntax {
+                        line,
+                        message: format!("expected {} tiles but found {}", n, row.len()),
+                    });
+                }
+                values.extend(row);
+            }
+            (n, values)
+        };
+
+        if n < Self::MIN_SIZE {
+            return Err(PuzzleError::InvalidSize(n));
+        }
+
+        BoardManager::check_permutation(n, &values)?;
+
+        Ok((
+            n as u8,
+            values.into_iter().map(|value| value as u8).collect(),
+        ))
+    }
+
+    fn build(
+        n: u8,
+        tiles: Vec<u8>,
+        goal_state: Vec<u8>,
+        informed: bool,
+    ) -> Result<Board, PuzzleError> {
+        if goal_state.len() != tiles.len() {
+            return Err(PermutationError::LengthMismatch {
+                expected: tiles.len(),
+                found: goal_state.len(),
+            }
+            .into());
+        }
+
+        let tiles: Vec<Tile> = tiles.into_iter().map(Tile::with_value).collect();
+        let goal: Vec<Tile> = goal_state
+            .iter()
+            .map(|&value| Tile::with_value(value))
+            .collect();
+
+        if !BoardManager::is_solvable(n, &tiles, &goal) {
+            return Err(PuzzleError::Unsolvable);
+        }
+
+        let mut builder = BoardConfigBuilder::builder().essential(n, goal_state);
+        if informed {
+            builder = builder.with_informed();
+        }
+        let config = builder.build()?;
+
+        Ok(Board::with_tiles(config, tiles))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::board::{board::BoardManager, goal_layout::GoalLayout};
+
+    use crate::error::{PermutationError, PuzzleError};
+
+    use super::BoardParser;
+
+    #[test]
+    fn it_should_parse_the_standard_file_format() {
+        let input = "# This puzzle is solvable\n3\n1 2 3 # first row\n4 5 6\n7 0 8\n";
+        let board = BoardParser::parse(input, false).unwrap();
+        let values: Vec<u8> = BoardManager::tiles_of(&board)
+            .iter()
+            .map(|tile| tile.get_value())
+            .collect();
+        assert_eq!(values, vec![1, 2, 3, 4, 5, 6, 7, 0, 8]);
+        assert_eq!(BoardManager::size_of(&board), 3);
+        assert_eq!(BoardManager::empty_tile_idx(&board), 7);
+    }
+
+    #[test]
+    fn it_should_parse_a_single_line() {
+        let board = BoardParser::parse("1 2 3 4 5 6 7 8 0", true).unwrap();
+        assert!(board.match_goal());
+    }
+
+    #[test]
+    fn it_should_parse_against_a_goal_layout() {
+        let board =
+            BoardParser::parse_with_layout("1 2 3 8 0 4 7 6 5", GoalLayout::Snail, false).unwrap();
+        assert!(board.match_goal());
+        assert_eq!(BoardManager::empty_tile_idx(&board), 4);
+    }
+
+    #[test]
+    fn it_should_report_malformed_boards() {
+        assert!(matches!(
+            BoardParser::parse_tiles("# nothing"),
+            Err(PuzzleError::Syntax { line: 0, .. })
+        ));
+        assert_eq!(
+            BoardParser::parse_tiles("1 2 3 4 5 6 7 0"),
+            Err(PuzzleError::InvalidSize(8))
+        );
+        assert!(matches!(
+            BoardParser::parse_tiles("3\n1 2 3\n4 5 6\n"),
+            Err(PuzzleError::Syntax { line: 3, .. })
+        ));
+        assert_eq!(
+            BoardParser::parse_tiles("1 2 3 4 5 6 7 7 0"),
+            Err(PermutationError::DuplicateTile(7).into())
+        );
+        assert_eq!(
+            BoardParser::parse_tiles("1 2 3 4 5 6 7 0 9"),
+            Err(PermutationError::TileOutOfRange { value: 9, max: 8 }.into())
+        );
+        assert_eq!(
+            BoardParser::parse_tiles("1 2 3 4 5 6 7 8 9"),
+            Err(PermutationError::MissingBlank.into())
+        );
+        assert!(matches!(
+            BoardParser::parse_tiles("2\n1 x\n3 0"),
+            Err(PuzzleError::Syntax { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn it_should_reject_unsolvable_boards() {
+        assert_eq!(
+            BoardParser::parse("2 1 3 4 5 6 7 8 0", false).unwrap_err(),
+            PuzzleError::Unsolvable
+        );
+    }
+}
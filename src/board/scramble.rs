@@ -0,0 +1,236 @@
+use std::collections::HashSet;
+
+use rand::{seq::SliceRandom, Rng, RngCore};
+
+use crate::error::{PermutationError, PuzzleError};
+
+use super::{board::BoardManager, cell::Tile};
+
+/// Ways of producing a solvable starting board from the goal state.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Scramble {
+    /// A uniformly random board among all boards that can reach the goal.
+    Uniform,
+    /// A random walk of the empty tile that never immediately undoes its previous move.
+    Walk(usize),
+    /// A random board whose optimal solution takes exactly this many moves, found by
+    /// enumerating the breadth-first layers around the goal.
+    Distance(usize),
+}
+
+impl Scramble {
+    /// Upper bound on the size of a breadth-first layer kept by [`Scramble::Distance`].
+    pub const MAX_LAYER_SIZE: usize = 4_000_000;
+
+    pub(crate) fn generate(
+        &self,
+        n: u8,
+        goal_state: &[Tile],
+        rng: &mut dyn RngCore,
+    ) -> Result<(Vec<Tile>, u8), PuzzleError> {
+        let empty_tile_idx = goal_state
+            .iter()
+            .position(|tile| *tile == Tile::Empty)
+            .ok_or(PermutationError::MissingBlank)?;
+
+        let tiles = match self {
+            Self::Uniform => Self::uniform(n, goal_state, rng),
+            Self::Walk(moves) => Self::walk(n, goal_state, empty_tile_idx, *moves, rng),
+            Self::Distance(distance) => Self::distance(n, goal_state, *distance, rng)?,
+        };
+
+        let empty_tile_idx = tiles.iter().position(|tile| *tile == Tile::Empty).unwrap();
+
+        Ok((tiles, empty_tile_idx as u8))
+    }
+
+    fn uniform(n: u8, goal_state: &[Tile], rng: &mut dyn RngCore) -> Vec<Tile> {
+        let mut tiles = goal_state.to_vec();
+        tiles.shuffle(rng);
+
+        // swapping two fixed tiles pairs every unsolvable board with exactly one solvable one,
+        // so fixing the parity keeps the distribution uniform
+        if !BoardManager::is_solvable(n, &tiles, goal_state) {
+            let mut values = tiles
+                .iter()
+                .enumerate()
+                .filter(|(_, tile)| **tile != Tile::Empty)
+                .map(|(idx, _)| idx);
+            let (first, second) = (values.next().unwrap(), values.next().unwrap());
+            tiles.swap(first, second);
+        }
+
+        tiles
+    }
+
+    fn walk(
+        n: u8,
+        goal_state: &[Tile],
+        mut idx: usize,
+        moves: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<Tile> {
+        let mut tiles = goal_state.to_vec();
+        let mut previous = None;
+
+        for _ in 0..moves {
+            let candidates: Vec<usize> = BoardManager::adjacent_of(idx, n as usize)
+                .into_iter()
+                .filter(|next| Some(*next) != previous)
+                .collect();
+            let next = candidates[rng.gen_range(0..candidates.len())];
+            tiles.swap(idx, next);
+            previous = Some(idx);
+            idx = next;
+        }
+
+        tiles
+    }
+
+    fn distance(
+        n: u8,
+        goal_state: &[Tile],
+        distance: usize,
+        rng: &mut dyn RngCore,
+    ) -> Result<Vec<Tile>, PuzzleError> {
+        let goal: Vec<u8> = goal_state.iter().map(Tile::get_value).collect();
+
+        // the state graph is bipartite, so the next layer is every neighbour of the current
+        // layer that does not belong to the previous one
+        let mut previous: HashSet<Vec<u8>> = HashSet::new();
+        let mut current: HashSet<Vec<u8>> = HashSet::from([goal]);
+
+        for _ in 0..distance {
+            let mut next = HashSet::new();
+            for tiles in &current {
+                let idx = tiles.iter().position(|value| *value == 0).unwrap();
+                for neighbor in BoardManager::adjacent_of(idx, n as usize) {
+                    let mut state = tiles.clone();
+                    state.swap(idx, neighbor);
+                    if !previous.contains(&state) {
+                        next.insert(state);
+                    }
+                }
+            }
+            if next.is_empty() {
+                return Err(PuzzleError::DistanceOutOfRange(distance));
+            }
+            if next.len() > Self::MAX_LAYER_SIZE {
+                return Err(PuzzleError::LimitExceeded {
+                    limit: Self::MAX_LAYER_SIZE,
+                });
+            }
+            previous = current;
+            current = next;
+        }
+
+        // sorted so that the pick only depends on the generator
+        let mut layer: Vec<Vec<u8>> = current.into_iter().collect();
+        layer.sort_unstable();
+        let tiles = layer.swap_remove(rng.gen_range(0..layer.len()));
+
+        Ok(tiles.into_iter().map(Tile::with_value).collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{HashSet, VecDeque};
+
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::{
+        board::{board::BoardManager, cell::Tile, goal_layout::GoalLayout},
+        error::PuzzleError,
+    };
+
+    use super::Scramble;
+
+    fn goal(n: u8, layout: GoalLayout) -> Vec<Tile> {
+        layout
+            .goal_state(n)
+            .into_iter()
+            .map(Tile::with_value)
+            .collect()
+    }
+
+    fn optimal_distance(n: u8, tiles: &[Tile], goal_state: &[Tile]) -> usize {
+        let mut visited = HashSet::from([tiles.to_vec()]);
+        let mut queue = VecDeque::from([(tiles.to_vec(), 0)]);
+        while let Some((tiles, distance)) = queue.pop_front() {
+            if tiles == goal_state {
+                return distance;
+            }
+            let idx = tiles.iter().position(|tile| *tile == Tile::Empty).unwrap();
+            for neighbor in BoardManager::adjacent_of(idx, n as usize) {
+                let mut state = tiles.clone();
+                state.swap(idx, neighbor);
+                if visited.insert(state.clone()) {
+                    queue.push_back((state, distance + 1));
+                }
+            }
+        }
+        unreachable!()
+    }
+
+    #[test]
+    fn it_should_only_generate_solvable_boards() {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        for layout in [
+            GoalLayout::RowMajor,
+            GoalLayout::BlankFirst,
+            GoalLayout::Snail,
+        ] {
+            for n in 2..=5 {
+                let goal_state = goal(n, layout);
+                for scramble in [Scramble::Uniform, Scramble::Walk(50)] {
+                    let (tiles, idx) = scramble.generate(n, &goal_state, &mut rng).unwrap();
+                    assert!(BoardManager::is_solvable(n, &tiles, &goal_state));
+                    assert_eq!(tiles[idx as usize], Tile::Empty);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_generate_boards_at_an_exact_distance() {
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
+        for layout in [GoalLayout::RowMajor, GoalLayout::Snail] {
+            let goal_state = goal(3, layout);
+            for distance in [0, 1, 7, 12] {
+                let (tiles, _) = Scramble::Distance(distance)
+                    .generate(3, &goal_state, &mut rng)
+                    .unwrap();
+                assert_eq!(optimal_distance(3, &tiles, &goal_state), distance);
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_fail_beyond_the_diameter() {
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        let goal_state = goal(2, GoalLayout::RowMajor);
+        assert!(Scramble::Distance(6)
+            .generate(2, &goal_state, &mut rng)
+            .is_ok());
+        assert_eq!(
+            Scramble::Distance(7).generate(2, &goal_state, &mut rng),
+            Err(PuzzleError::DistanceOutOfRange(7))
+        );
+    }
+
+    #[test]
+    fn it_should_never_undo_the_previous_move() {
+        let mut rng = ChaCha8Rng::seed_from_u64(4);
+        let goal_state = goal(2, GoalLayout::RowMajor);
+        // on a 2x2 board a walk without backtracking circles around the board, so it
+        // ends up at distance 2 after two moves instead of possibly returning home
+        for _ in 0..20 {
+            let (tiles, _) = Scramble::Walk(2)
+                .generate(2, &goal_state, &mut rng)
+                .unwrap();
+            assert_eq!(optimal_distance(2, &tiles, &goal_state), 2);
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::{PermutationError, PuzzleError};
+
+use super::{
+    board::{Board, BoardManager},
+    board_config::{BoardConfig, InformedConfig, UninformedConfig},
+    cell::Tile,
+};
+
+/// Stable JSON shape of a [`BoardConfig`]: `depth` and `weight` only appear for informed
+/// configs.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct BoardConfigRepr {
+    size: u8,
+    goal: Vec<u8>,
+    blank: u8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    depth: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    weight: Option<usize>,
+}
+
+impl From<BoardConfig> for BoardConfigRepr {
+    fn from(config: BoardConfig) -> Self {
+        Self {
+            size: config.n(),
+            goal: config.goal_state().iter().map(Tile::get_value).collect(),
+            blank: config.empty_tile_idx(),
+            depth: config.get_depth().copied(),
+            weight: config.get_weight().copied(),
+        }
+    }
+}
+
+impl TryFrom<BoardConfigRepr> for BoardConfig {
+    type Error = PuzzleError;
+
+    fn try_from(repr: BoardConfigRepr) -> Result<Self, Self::Error> {
+        if !(2..=16).contains(&repr.size) {
+            return Err(PuzzleError::InvalidSize(repr.size as usize));
+        }
+        let values: Vec<usize> = repr.goal.iter().map(|value| *value as usize).collect();
+        BoardManager::check_permutation(repr.size as usize, &values)?;
+        let cells = repr.goal.len();
+        if repr.blank as usize >= cells {
+            return Err(PuzzleError::BlankOutOfRange {
+                index: repr.blank as usize,
+                cells,
+            });
+        }
+
+        let goal_state = repr.goal.into_iter().map(Tile::with_value).collect();
+        Ok(if repr.depth.is_none() && repr.weight.is_none() {
+            BoardConfig::Uninformed(UninformedConfig {
+                n: repr.size,
+                empty_tile_idx: repr.blank,
+                goal_state,
+            })
+        } else {
+            BoardConfig::Informed(InformedConfig {
+                n: repr.size,
+                empty_tile_idx: repr.blank,
+                goal_state,
+                weight: repr.weight.unwrap_or(0),
+                depth: repr.depth.unwrap_or(0),
+            })
+        })
+    }
+}
+
+/// Stable JSON shape of a [`Board`]: its size, tiles, goal and blank index, followed by the
+/// search bookkeeping and scramble seed when present.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct BoardRepr {
+    size: u8,
+    tiles: Vec<u8>,
+    goal: Vec<u8>,
+    blank: u8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    depth: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    weight: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+}
+
+impl From<Board> for BoardRepr {
+    fn from(board: Board) -> Self {
+        let config = BoardConfigRepr::from(BoardManager::config_of(&board).clone());
+        Self {
+            size: config.size,
+            tiles: BoardManager::tiles_of(&board)
+                .iter()
+                .map(Tile::get_value)
+                .collect(),
+            goal: config.goal,
+            blank: config.blank,
+            depth: config.depth,
+            weight: config.weight,
+            seed: board.seed(),
+        }
+    }
+}
+
+impl TryFrom<BoardRepr> for Board {
+    type Error = PuzzleError;
+
+    fn try_from(repr: BoardRepr) -> Result<Self, Self::Error> {
+        let config = BoardConfig::try_from(BoardConfigRepr {
+            size: repr.size,
+            goal: repr.goal,
+            blank: repr.blank,
+            depth: repr.depth,
+            weight: repr.weight,
+        })?;
+
+        let values: Vec<usize> = repr.tiles.iter().map(|value| *value as usize).collect();
+        BoardManager::check_permutation(repr.size as usize, &values)?;
+        if repr.tiles.get(repr.blank as usize) != Some(&0) {
+            return Err(PermutationError::MissingBlank.into());
+        }
+
+        let tiles: Vec<Tile> = repr.tiles.into_iter().map(Tile::with_value).collect();
+        if !BoardManager::is_solvable(repr.size, &tiles, config.goal_state()) {
+            return Err(PuzzleError::Unsolvable);
+        }
+
+        let mut board = Board::with_tiles(config, tiles);
+        board.set_seed(repr.seed);
+        Ok(board)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        algorithms::report::SolveReport,
+        board::{board::BoardManager, moves::Move, parser::BoardParser},
+        Board, BoardConfig, PuzzleError,
+    };
+
+    use super::BoardConfigRepr;
+
+    #[test]
+    fn it_should_serialize_boards_to_a_stable_shape() {
+        let board = BoardParser::parse("1 2 3 4 0 6 7 5 8", false).unwrap();
+        let json = serde_json::to_string(&board).unwrap();
+        assert_eq!(
+            json,
+            r#"{"size":3,"tiles":[1,2,3,4,0,6,7,5,8],"goal":[1,2,3,4,5,6,7,8,0],"blank":4}"#
+        );
+
+        let informed = BoardParser::parse("1 2 3 4 5 6 7 8 0", true).unwrap();
+        let json = serde_json::to_value(&informed).unwrap();
+        assert_eq!(json["depth"], 0);
+        assert_eq!(json["weight"], 0);
+    }
+
+    #[test]
+    fn it_should_round_trip_boards_and_configs() {
+        let board = BoardParser::parse("1 2 3 4 0 6 7 5 8", true).unwrap();
+        let json = serde_json::to_string(&board).unwrap();
+        let parsed: Board = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, board);
+        assert_eq!(
+            BoardManager::config_of(&parsed),
+            BoardManager::config_of(&board)
+        );
+
+        let config = BoardManager::config_of(&board).clone();
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(serde_json::from_str::<BoardConfig>(&json).unwrap(), config);
+    }
+
+    #[test]
+    fn it_should_reject_invalid_boards() {
+        let json = r#"{"size":3,"tiles":[2,1,3,4,5,6,7,8,0],"goal":[1,2,3,4,5,6,7,8,0],"blank":8}"#;
+        assert!(serde_json::from_str::<Board>(json).is_err());
+        let json = r#"{"size":3,"tiles":[1,2,3,4,5,6,7,8,0],"goal":[1,2,3,4,5,6,7,8,0],"blank":4}"#;
+        assert!(serde_json::from_str::<Board>(json).is_err());
+    }
+
+    #[test]
+    fn it_should_reject_configs_with_the_blank_off_the_board() {
+        let json = r#"{"size":3,"goal":[1,2,3,4,5,6,7,8,0],"blank":9}"#;
+        let repr: BoardConfigRepr = serde_json::from_str(json).unwrap();
+        let err = BoardConfig::try_from(repr).unwrap_err();
+        assert_eq!(err, PuzzleError::BlankOutOfRange { index: 9, cells: 9 });
+        assert!(serde_json::from_str::<BoardConfig>(json).is_err());
+    }
+
+    #[test]
+    fn it_should_serialize_solve_reports() {
+        let start = BoardParser::parse("1 2 3 4 5 6 0 7 8", false).unwrap();
+        let middle = BoardParser::parse("1 2 3 4 5 6 7 0 8", false).unwrap();
+        let goal = BoardParser::parse("1 2 3 4 5 6 7 8 0", false).unwrap();
+        let report = SolveReport::from_path(&[start, middle, goal].into_iter().collect()).unwrap();
+        assert_eq!(report.moves, vec![Move::Right, Move::Right]);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["moves"], serde_json::json!(["right", "right"]));
+        assert_eq!(json["length"], 2);
+        assert_eq!(json["initial"]["blank"], 6);
+    }
+}
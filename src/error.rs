@@ -0,0 +1,118 @@
+use std::{error::Error, fmt::Display};
+
+/// Ways the tiles of a board can fail to be a permutation of its goal state.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PermutationError {
+    TileOutOfRange { value: usize, max: usize },
+    DuplicateTile(u8),
+    MissingBlank,
+    LengthMismatch { expected: usize, found: usize },
+}
+
+impl Display for PermutationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TileOutOfRange { value, max } => write!(
+                f,
+                "tile {} is out of range (must be within 0 and {})",
+                value, max
+            ),
+            Self::DuplicateTile(value) => write!(f, "tile {} appears more than once", value),
+            Self::MissingBlank => write!(f, "there is no blank tile (0)"),
+            Self::LengthMismatch { expected, found } => {
+                write!(f, "expected {} tiles but found {}", expected, found)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum PuzzleError {
+    /// The input text is malformed; `line` is 1-based, or 0 when the whole input is at fault.
+    Syntax {
+        line: usize,
+        message: String,
+    },
+    InvalidSize(usize),
+    InvalidPermutation(PermutationError),
+    Unsolvable,
+    InvalidTemperature(f32),
+    UnknownLayout(String),
+    MissingField(&'static str),
+    ConflictingFields(&'static str, &'static str),
+    /// No board lies this many moves away from the goal.
+    DistanceOutOfRange(usize),
+    /// The blank index does not fall on a cell of the board.
+    BlankOutOfRange {
+        index: usize,
+        cells: usize,
+    },
+    LimitExceeded {
+        limit: usize,
+    },
+    Io(String),
+    NoSolution,
+    /// The solve was cancelled after doing this much work.
+    Cancelled {
+        expanded: usize,
+        generated: usize,
+    },
+}
+
+impl Display for PuzzleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Syntax { line, message } => write!(f, "line {}: {}", line, message),
+            Self::InvalidSize(size) => write!(
+                f,
+                "size {} is not supported (must be within 2 and 16)",
+                size
+            ),
+            Self::InvalidPermutation(err) => write!(f, "invalid board: {}", err),
+            Self::Unsolvable => write!(f, "board cannot reach the goal state"),
+            Self::InvalidTemperature(temperature) => write!(
+                f,
+                "temperature {} must be within 0.0 and 1.0 (inclusive)",
+                temperature
+            ),
+            Self::UnknownLayout(name) => write!(
+                f,
+                "unknown goal layout '{}' (expected one of row-major, blank-first, snail)",
+                name
+            ),
+            Self::MissingField(field) => write!(f, "{} must be provided", field),
+            Self::ConflictingFields(first, second) => {
+                write!(f, "only one of {} and {} can be provided", first, second)
+            }
+            Self::DistanceOutOfRange(distance) => write!(
+                f,
+                "no board lies {} moves away from the goal state",
+                distance
+            ),
+            Self::BlankOutOfRange { index, cells } => write!(
+                f,
+                "blank index {} is out of range (must be below {})",
+                index, cells
+            ),
+            Self::LimitExceeded { limit } => write!(f, "search exceeded its limit of {}", limit),
+            Self::Io(message) => write!(f, "{}", message),
+            Self::NoSolution => write!(f, "no solution was found"),
+            Self::Cancelled {
+                expanded,
+                generated,
+            } => write!(
+                f,
+                "cancelled after expanding {} and generating {} boards",
+                expanded, generated
+            ),
+        }
+    }
+}
+
+impl Error for PuzzleError {}
+
+impl From<PermutationError> for PuzzleError {
+    fn from(err: PermutationError) -> Self {
+        Self::InvalidPermutation(err)
+    }
+}
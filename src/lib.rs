@@ -1,15 +1,49 @@
 mod algorithms;
 mod board;
+mod error;
 pub mod start_up;
 
 pub use algorithms::{
+    cancellation::{CancellationToken, SearchResult, SolveOutcome},
+    dot::DotRecorder,
     informed_search::{
-        heuristic::inversion_distance, heuristic::mahattan_distance, AStarAlgorithms,
+        beam::{BeamSearchAlgorithms, BeamSelection},
+        bidirectional::MmAlgorithms,
+        hda_star::HdaStarAlgorithms,
+        heuristic::gaschnig,
+        heuristic::inversion_distance,
+        heuristic::mahattan_distance,
+        heuristic::misplaced_tiles,
+        heuristic::out_of_line,
+        heuristic::perfect,
+        heuristic::walking_distance,
+        heuristic::HeuristicFn,
+        ida_star::{IdaStarMode, ParallelIdaStarAlgorithms},
+        rbfs::RbfsAlgorithms,
+        search_node::SearchNode,
+        sma_star::SmaStarAlgorithms,
+        AStarAlgorithms, AStarStepper,
+    },
+    observer::{NoopObserver, SearchObserver, SearchStats},
+    pruning::MovePruning,
+    reduction::ReductionAlgorithms,
+    report::{HeuristicComparison, SolveReport},
+    state_space::StateSpace,
+    stepper::{SearchStepper, Step},
+    uninformed_search::{
+        dfs::{DfsAlgorithms, DfsStepper},
+        optimal::OptimalSolutions,
+        ucs::{UcsAlgorithms, UcsStepper},
     },
-    uninformed_search::{dfs::DfsAlgorithms, ucs::UcsAlgorithms},
     Algorithms,
 };
 pub use board::{
     board::{Board, BoardBuilder, Temperature},
+    cell::Tile,
+    goal_layout::GoalLayout,
+    moves::Move,
+    parser::BoardParser,
+    scramble::Scramble,
     BoardConfig, BoardConfigBuilder,
 };
+pub use error::{PermutationError, PuzzleError};
@@ -1,16 +1,88 @@
-use n_puzzle_trial::{inversion_distance::InversionDistance, start_up::run, AStarAlgorithms};
+use std::{env, process, thread, time::Duration};
+
+use n_puzzle_trial::{
+    inversion_distance::InversionDistance,
+    start_up::{run, run_file},
+    AStarAlgorithms, CancellationToken, GoalLayout,
+};
+
+const USAGE: &str =
+    "usage: n-puzzle-trial [--size N] [--goal row-major|blank-first|snail] [--seed SEED] [--timeout SECONDS] [FILE]";
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    process::exit(2);
+}
+
+/// Cancels `token` on Ctrl-C, so the solve stops and reports how far it got.
+fn cancel_on_interrupt(token: &CancellationToken) {
+    let token = token.clone();
+    if let Err(err) = ctrlc::set_handler(move || token.cancel()) {
+        eprintln!("warning: Ctrl-C will not cancel the solve: {}", err);
+    }
+}
 
 fn main() {
-    let n = 3;
+    let mut n = 3;
+    let mut layout = GoalLayout::RowMajor;
+    let mut seed = None;
+    let mut timeout = None;
+    let mut path = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" => {
+                n = args
+                    .next()
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or_else(|| usage());
+            }
+            "--goal" => {
+                layout = args
+                    .next()
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or_else(|| usage());
+            }
+            "--seed" => {
+                seed = Some(
+                    args.next()
+                        .and_then(|value| value.parse().ok())
+                        .unwrap_or_else(|| usage()),
+                );
+            }
+            "--timeout" => {
+                timeout = Some(
+                    args.next()
+                        .and_then(|value| value.parse().ok())
+                        .map(Duration::from_secs_f64)
+                        .unwrap_or_else(|| usage()),
+                );
+            }
+            _ => path = Some(arg),
+        }
+    }
+
+    let algorithms = Box::new(AStarAlgorithms::with(Box::new(InversionDistance::new())));
 
-    let mut goal_state: Vec<u8> = (1..(n * n)).collect();
+    let token = CancellationToken::new();
+    cancel_on_interrupt(&token);
+    if let Some(timeout) = timeout {
+        let token = token.clone();
+        thread::spawn(move || {
+            thread::sleep(timeout);
+            token.cancel();
+        });
+    }
 
-    goal_state.push(0);
+    // an instance file is solved against the chosen goal, otherwise a random board is generated
+    let result = match path {
+        Some(path) => run_file(algorithms, &path, layout, true, &token),
+        None => run(algorithms, n, layout.goal_state(n), true, seed, &token),
+    };
 
-    run(
-        Box::new(AStarAlgorithms::with(Box::new(InversionDistance::new()))),
-        n,
-        goal_state.to_vec(),
-        true,
-    );
+    if let Err(err) = result {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
 }
@@ -1,22 +1,74 @@
+use std::fs;
+
 use crate::{
-    board::board::BoardManager, Algorithms, BoardBuilder, BoardConfigBuilder, Temperature,
+    Algorithms, Board, BoardBuilder, BoardConfigBuilder, BoardParser, CancellationToken,
+    GoalLayout, PuzzleError, SolveOutcome, Temperature,
 };
 
-pub fn run(mut algorithms: Box<dyn Algorithms>, n: u8, goal_state: Vec<u8>, informed: bool) {
+pub fn run(
+    algorithms: Box<dyn Algorithms>,
+    n: u8,
+    goal_state: Vec<u8>,
+    informed: bool,
+    seed: Option<u64>,
+    token: &CancellationToken,
+) -> Result<(), PuzzleError> {
     let mut builder = BoardConfigBuilder::builder().essential(n, goal_state);
 
     if informed {
         builder = builder.with_informed();
     }
 
-    let config = builder.build().expect("Fail to build config");
+    let config = builder.build()?;
 
-    let board = BoardBuilder::builder()
+    let mut builder = BoardBuilder::builder()
         .config(config)
-        .temperature(Temperature(0.1))
-        .build()
-        .expect("Fail to build board");
-    let answers = algorithms.solve(board).expect("No solution");
+        .temperature(Temperature(0.1));
+
+    if let Some(seed) = seed {
+        builder = builder.seed(seed);
+    }
+
+    let board = builder.build()?;
+
+    if let Some(seed) = board.seed() {
+        println!("seed: {}", seed);
+    }
+
+    solve(algorithms, board, token)
+}
+
+pub fn run_file(
+    algorithms: Box<dyn Algorithms>,
+    path: &str,
+    layout: GoalLayout,
+    informed: bool,
+    token: &CancellationToken,
+) -> Result<(), PuzzleError> {
+    let input =
+        fs::read_to_string(path).map_err(|err| PuzzleError::Io(format!("{}: {}", path, err)))?;
 
-    answers.iter().for_each(|answer| println!("{}", answer));
+    let board = BoardParser::parse_with_layout(&input, layout, informed)?;
+
+    solve(algorithms, board, token)
+}
+
+fn solve(
+    mut algorithms: Box<dyn Algorithms>,
+    board: Board,
+    token: &CancellationToken,
+) -> Result<(), PuzzleError> {
+    let result = algorithms.solve_until_cancelled(board, token);
+
+    match result.outcome {
+        SolveOutcome::Solved(answers) => {
+            answers.iter().for_each(|answer| println!("{}", answer));
+            Ok(())
+        }
+        SolveOutcome::Exhausted => Err(PuzzleError::NoSolution),
+        SolveOutcome::Cancelled => Err(PuzzleError::Cancelled {
+            expanded: result.stats.expanded,
+            generated: result.stats.generated,
+        }),
+    }
 }